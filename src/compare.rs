@@ -6,6 +6,8 @@ use crate::pgdiff::{DiffSource, SchemaDiff};
 use postgres::row::Row;
 use std::collections::BTreeMap;
 
+pub const PG_9_1: u32 = 90100;
+pub const PG_9_2: u32 = 90200;
 pub const PG_9_3: u32 = 90300;
 pub const PG_9_4: u32 = 90400;
 pub const PG_9_5: u32 = 90500;
@@ -15,6 +17,10 @@ pub const PG_11: u32 = 110000;
 pub const PG_12: u32 = 120000;
 pub const PG_13: u32 = 130000;
 pub const PG_14: u32 = 140000;
+pub const PG_15: u32 = 150000;
+pub const PG_16: u32 = 160000;
+pub const PG_17: u32 = 170000;
+pub const PG_18: u32 = 180000;
 
 pub const PG_MIN: u32 = 0;
 pub const PG_MAX: u32 = u32::MAX;
@@ -43,11 +49,56 @@ macro_rules! proc_prototype {
     };
 }
 
+// Callback used to compare two elements having the same key in compare_map
+pub type MapCmpFn<'a, T> = fn(&'a str, &'a T, &'a T, &mut Vec<SchemaDiff<'a>>);
+
+// Initial privileges of an object, as recorded in pg_init_privs.  This is
+// what pg_dump relies on to know which ACLs were set by the extension script.
+#[macro_export]
+macro_rules! init_privs {
+    ($classid:literal, $objoid:literal) => {
+        init_privs!($classid, $objoid, "0")
+    };
+    ($classid:literal, $objoid:literal, $objsubid:literal) => {
+        format!(
+            "(SELECT ip.privtype::text || ': ' || ip.initprivs::text \
+            FROM pg_init_privs ip \
+            WHERE ip.classoid = '{classid}'::regclass \
+            AND ip.objoid = {objoid} \
+            AND ip.objsubid = {objsubid})",
+            classid = $classid,
+            objoid = $objoid,
+            objsubid = $objsubid
+        )
+    };
+}
+
+// Security labels of an object, as recorded in pg_seclabel, in the format
+// expected by SecLabels.
+#[macro_export]
+macro_rules! seclabels {
+    ($classid:literal, $objoid:literal) => {
+        seclabels!($classid, $objoid, "0")
+    };
+    ($classid:literal, $objoid:literal, $objsubid:literal) => {
+        format!(
+            "(SELECT array_agg(s.provider || '=' || s.label) \
+            FROM pg_seclabel s \
+            WHERE s.classoid = '{classid}'::regclass \
+            AND s.objoid = {objoid} \
+            AND s.objsubid = {objsubid})",
+            classid = $classid,
+            objoid = $objoid,
+            objsubid = $objsubid
+        )
+    };
+}
+
 pub fn compare_map<'a, T>(
     self_map: &'a BTreeMap<String, T>,
     other_map: &'a BTreeMap<String, T>,
     typname: &'static str,
-    fn_comp: Option<fn(&'a str, &'a T, &'a T, &mut Vec<SchemaDiff<'a>>)>,
+    fn_comp: Option<MapCmpFn<'a, T>>,
 ) -> Option<SchemaDiff<'a>> {
     let mut missings: Vec<(DiffSource, Vec<&str>)> = Vec::new();
     let mut diffs = Vec::new();
@@ -199,8 +250,7 @@ impl<'a, T: Compare<'a>> Compare<'a> for Option<T> {
     fn value(&self) -> String {
         match self {
             None => {
-                assert!(false, "Should not be called");
-                panic!();
+                panic!("Should not be called");
             }
             Some(v) => v.value(),
         }
@@ -377,16 +427,23 @@ macro_rules! DbStruct {
                                     _as,
                             ));
                     } else {
-                        let mut typname = stringify!($type).to_lowercase();
+                        let mut typname = stringify!($type)
+                            .to_lowercase()
+                            .replace(' ', "");
+                        // NULL is already a valid value for an Option<>
+                        if let Some(t) = typname
+                            .strip_prefix("option<")
+                            .and_then(|t| t.strip_suffix('>'))
+                        {
+                            typname = String::from(t);
+                        }
+
                         if typname == "char" {
                             typname = String::from("\"char\"");
                         } else if typname == "list"
                             || typname == "classoptions"
                         {
                             typname = String::from("text[]");
-                        } else if typname == "option<text>" {
-                            // FIXME should find a way to handle any Option<>
-                            typname = String::from("text");
                         }
 
                         tlist.push(format!(
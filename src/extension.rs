@@ -4,18 +4,28 @@
  *---------------------------------------------------------------------------*/
 use std::collections::BTreeMap;
 
-use postgres::Transaction;
+use postgres::{Client, Transaction};
 
 mod pg_cast;
 use pg_cast::Cast;
 mod pg_class;
 use pg_class::Relation;
+mod pg_collation;
+use pg_collation::Collation;
+mod pg_conversion;
+use pg_conversion::Conversion;
+mod pg_depend;
+use pg_depend::{Dependencies, UnhandledObject};
 mod pg_event_trigger;
 use pg_event_trigger::EventTrigger;
 mod pg_extconfig;
 use pg_extconfig::ExtConfig;
+mod pg_extension;
+use pg_extension::PgExtension;
 mod pg_foreign_data_wrapper;
 use pg_foreign_data_wrapper::ForeignDataWrapper;
+mod pg_foreign_server;
+use pg_foreign_server::ForeignServer;
 mod pg_namespace;
 use pg_namespace::Namespace;
 mod pg_opclass;
@@ -26,62 +36,109 @@ mod pg_operator;
 use pg_operator::Operator;
 mod pg_proc;
 use pg_proc::Routine;
+mod pg_settings;
+use pg_settings::Settings;
 mod pg_type;
 use pg_type::Type;
 
-use crate::{compare::*, pgdiff::SchemaDiff, pgtype::ExecutedQueries, CompareStruct};
+use crate::{compare::*, elog::*, pgdiff::SchemaDiff, pgtype::ExecutedQueries, CompareStruct};
 
 mod pg_aggregate;
+mod pg_amop;
+mod pg_amproc;
 mod pg_attribute;
 mod pg_constraint;
+mod pg_foreign_table;
 mod pg_index;
+mod pg_inherits;
 mod pg_policy;
 mod pg_range;
 mod pg_rewrite;
+mod pg_sequence;
 mod pg_statistic_ext;
+use pg_statistic_ext::{ExtendedStatistic, PG_MIN_VER as EXT_STATS_MIN_VER};
 mod pg_trigger;
+mod pg_user_mapping;
+mod pg_view;
 
 CompareStruct! {
     Extension {
+        extension: PgExtension,
         relations: Option<BTreeMap<String, Relation>>,
         extension_config: ExtConfig,
+        settings: Settings,
         routines: Option<BTreeMap<String, Routine>>,
         event_triggers: Option<BTreeMap<String, EventTrigger>>,
         operators: Option<BTreeMap<String, Operator>>,
         types: Option<BTreeMap<String, Type>>,
         casts: Option<BTreeMap<String, Cast>>,
+        collations: Option<BTreeMap<String, Collation>>,
+        conversions: Option<BTreeMap<String, Conversion>>,
         foreign_data_wrappers: Option<BTreeMap<String, ForeignDataWrapper>>,
+        foreign_servers: Option<BTreeMap<String, ForeignServer>>,
         namespaces: Option<BTreeMap<String, Namespace>>,
         opclasses: Option<BTreeMap<String, OpClass>>,
         opfamilies: Option<BTreeMap<String, OpFamily>>,
+        statistics: Option<BTreeMap<String, ExtendedStatistic>>,
+        unhandled_objects: Option<BTreeMap<String, UnhandledObject>>,
+        dependencies: Dependencies,
         extra_queries: ExecutedQueries,
     }
 }
 
+// Optional checks, disabled by default
+#[derive(Clone, Copy, Default)]
+pub struct SnapshotOptions {
+    // Compare the current state of the sequences (last_value and is_called)
+    pub sequence_values: bool,
+    // Error out if an extension member's catalog isn't handled
+    pub strict: bool,
+    // Compare the physical layout of the relations and composite types
+    pub physical_layout: bool,
+}
+
 impl Extension {
-    pub fn snapshot(extname: &str, client: &mut Transaction, pgver: u32) -> Self {
+    pub fn snapshot(
+        extname: &str,
+        client: &mut Transaction,
+        new_session: &dyn Fn() -> Result<Client, postgres::Error>,
+        pgver: u32,
+        options: SnapshotOptions,
+    ) -> Self {
+        let extension = PgExtension::snapshot(client, extname, pgver);
         let extension_config = ExtConfig::snapshot(client, extname);
+        let settings = Settings::snapshot(client, new_session, extname, pgver);
+
+        client
+            .execute("SET search_path TO pg_catalog", &[])
+            .expect("Could not secure search_path");
+
+        let dependencies = Dependencies::snapshot(client, extname, pgver);
 
         let mut ext = Extension {
             ident: String::from(extname),
+            extension,
             relations: None,
             extension_config,
+            settings,
             routines: None,
             event_triggers: None,
             operators: None,
             types: None,
             casts: None,
+            collations: None,
+            conversions: None,
             foreign_data_wrappers: None,
+            foreign_servers: None,
             namespaces: None,
             opclasses: None,
             opfamilies: None,
+            statistics: None,
+            unhandled_objects: None,
+            dependencies,
             extra_queries: ExecutedQueries::new(),
         };
 
-        client
-            .execute("SET search_path TO pg_catalog", &[])
-            .expect("Could not secure search_path");
-
         let dependencies = client
             .query(
                 "SELECT classid::regclass::text, array_agg(objid) \
@@ -103,19 +160,26 @@ impl Extension {
                     ext.casts = Some(Cast::snapshot(client, objids, pgver));
                 }
                 "pg_class" => {
-                    ext.relations = Some(Relation::snapshot(client, objids, pgver));
+                    ext.relations = Some(Relation::snapshot(client, objids, pgver, options));
+                }
+                "pg_collation" => {
+                    ext.collations = Some(Collation::snapshot(client, objids, pgver));
+                }
+                "pg_conversion" => {
+                    ext.conversions = Some(Conversion::snapshot(client, objids, pgver));
                 }
                 "pg_event_trigger" => {
-                    assert!(
-                        pgver >= PG_9_3,
-                        "Event triggers were introduced in PostgreSQL 9.3"
-                    );
+                    // The catalog only exists since PostgreSQL 9.3, so older
+                    // servers can't reach this branch.
                     ext.event_triggers = Some(EventTrigger::snapshot(client, objids, pgver));
                 }
                 "pg_foreign_data_wrapper" => {
                     ext.foreign_data_wrappers =
                         Some(ForeignDataWrapper::snapshot(client, objids, pgver));
                 }
+                "pg_foreign_server" => {
+                    ext.foreign_servers = Some(ForeignServer::snapshot(client, objids, pgver));
+                }
                 "pg_namespace" => {
                     ext.namespaces = Some(Namespace::snapshot(client, objids, pgver));
                 }
@@ -131,11 +195,37 @@ impl Extension {
                 "pg_proc" => {
                     ext.routines = Some(Routine::snapshot(client, objids, pgver));
                 }
+                "pg_statistic_ext" => {
+                    assert!(
+                        pgver >= EXT_STATS_MIN_VER,
+                        "Extended statistics were introduced in PostgreSQL 10"
+                    );
+                    ext.statistics =
+                        Some(ExtendedStatistic::snapshot_members(client, objids, pgver));
+                }
                 "pg_type" => {
-                    ext.types = Some(Type::snapshot(client, objids, pgver));
+                    ext.types = Some(Type::snapshot(client, objids, pgver, options));
                 }
                 _ => {
-                    println!("Classid \"{}\" not handled", classid);
+                    if options.strict {
+                        elog(ERROR, &format!("Classid \"{}\" not handled", classid));
+                        panic!();
+                    }
+
+                    elog(
+                        WARNING,
+                        &format!(
+                            "Classid \"{}\" not handled, only comparing the objects identity",
+                            classid
+                        ),
+                    );
+                    UnhandledObject::snapshot(
+                        client,
+                        classid,
+                        objids,
+                        pgver,
+                        ext.unhandled_objects.get_or_insert_with(BTreeMap::new),
+                    );
                 }
             }
         }
@@ -147,7 +237,124 @@ impl Extension {
         ext
     }
 
+    pub fn extversion(&self) -> &str {
+        self.extension.extversion()
+    }
+
     pub fn set_extra_queries(&mut self, extra_queries: ExecutedQueries) {
         self.extra_queries = extra_queries;
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{
+        pg_attribute::Attribute, pg_collation::Collation, pg_constraint::Constraint,
+        pg_index::PgIndex, pg_proc::PgRoutine, pg_type::PgType,
+    };
+    use crate::compare::*;
+
+    // Check that the given columns are retrieved in the new target list but
+    // not in the previous one, and that the rest of the target list doesn't
+    // change.
+    fn check_new_columns(prev: Vec<String>, new: Vec<String>, cols: &[(&str, &str)]) {
+        assert_eq!(prev.len(), new.len());
+
+        for (col, fallback) in cols {
+            let fallback = format!("NULL::{} AS {}", fallback, col);
+
+            assert!(
+                prev.contains(&fallback),
+                "Previous target list should not retrieve \"{}\"\n{:#?}",
+                col,
+                prev
+            );
+            assert!(
+                !new.contains(&fallback),
+                "New target list should retrieve \"{}\"\n{:#?}",
+                col,
+                new
+            );
+        }
+
+        let changed = prev.iter().zip(new.iter()).filter(|(a, b)| a != b);
+        assert_eq!(
+            changed.count(),
+            cols.len(),
+            "Only {:?} should differ between the target lists",
+            cols
+        );
+    }
+
+    #[test]
+    fn test_tlist_pg91() {
+        check_new_columns(
+            PgRoutine::tlist(PG_9_1),
+            PgRoutine::tlist(PG_9_2),
+            &[("proleakproof", "bool")],
+        );
+        check_new_columns(
+            PgType::tlist(PG_9_1),
+            PgType::tlist(PG_9_2),
+            &[("typacl", "text")],
+        );
+        check_new_columns(
+            Attribute::tlist(PG_9_1),
+            Attribute::tlist(PG_9_2),
+            &[("attfdwoptions", "text[]")],
+        );
+    }
+
+    #[test]
+    fn test_tlist_recent_versions() {
+        check_new_columns(
+            PgRoutine::tlist(PG_13),
+            PgRoutine::tlist(PG_14),
+            &[("prosqlbody", "text")],
+        );
+        check_new_columns(
+            PgIndex::tlist(PG_14),
+            PgIndex::tlist(PG_15),
+            &[("indnullsnotdistinct", "bool")],
+        );
+        check_new_columns(
+            Collation::tlist(PG_14),
+            Collation::tlist(PG_15),
+            &[("colliculocale", "text")],
+        );
+        check_new_columns(
+            Collation::tlist(PG_15),
+            Collation::tlist(PG_16),
+            &[("collicurules", "text")],
+        );
+        check_new_columns(
+            Constraint::tlist(PG_17),
+            Constraint::tlist(PG_18),
+            &[("conenforced", "bool"), ("conperiod", "bool")],
+        );
+
+        // colliculocale was renamed to colllocale in pg17
+        let pg16 = Collation::tlist(PG_16);
+        let pg17 = Collation::tlist(PG_17);
+        assert!(pg16.contains(&String::from("colliculocale")));
+        assert!(pg16.contains(&String::from("NULL::text AS colllocale")));
+        assert!(pg17.contains(&String::from("NULL::text AS colliculocale")));
+        assert!(pg17.contains(&String::from("colllocale")));
+        assert_eq!(Collation::tlist(PG_18), pg17);
+
+        // attstattarget is NULL by default since pg17, previously -1, and is
+        // reported the same way for all versions.
+        let attstattarget = String::from("coalesce(attstattarget::int, -1) AS attstattarget");
+        for pgver in [PG_14, PG_17, PG_18] {
+            let tlist = Attribute::tlist(pgver);
+            let att = tlist.iter().find(|t| t.ends_with(" AS attstattarget"));
+
+            assert_eq!(
+                Some(&attstattarget),
+                att,
+                "attstattarget should be reported the same way for {}",
+                pgver
+            );
+        }
+    }
+}
@@ -54,9 +54,6 @@ impl Aggregate {
             }
         };
 
-        match row {
-            None => None,
-            Some(r) => Some(Aggregate::from_row(&r)),
-        }
+        row.map(|r| Aggregate::from_row(&r))
     }
 }
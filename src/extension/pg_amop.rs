@@ -0,0 +1,49 @@
+/*----------------------------------------------------------------------------
+ * Author: Julien Rouhaud
+ * Copyright: Copyright (c) 2021-2026 : Julien Rouhaud - All rights reserved
+ *---------------------------------------------------------------------------*/
+use postgres::{Row, Transaction};
+use std::collections::BTreeMap;
+
+use crate::{compare::*, opr_prototype, pgdiff::SchemaDiff, pgtype::*, DbStruct};
+
+DbStruct! {
+    AmOp:amopkey:FamilyOperator {
+        amopkey: Text = ("a.amopstrategy || ' (' || \
+            a.amoplefttype::regtype::text || ', ' || \
+            a.amoprighttype::regtype::text || ')'"),
+        amopstrategy: Smallint,
+        amoplefttype: Text = ("a.amoplefttype::regtype::text"),
+        amoprighttype: Text = ("a.amoprighttype::regtype::text"),
+        amopopr: Text = (opr_prototype!("o")),
+        amoppurpose: Char,
+        amopsortfamily: Option<Text> = ("sfn.nspname || '.' || sf.opfname"),
+    }
+}
+
+impl AmOp {
+    pub fn snapshot(client: &mut Transaction, opfid: u32, pgver: u32) -> BTreeMap<String, AmOp> {
+        let mut amops = BTreeMap::new();
+
+        let sql = format!(
+            "SELECT {} \
+            FROM pg_amop a \
+            JOIN pg_operator o ON o.oid = a.amopopr \
+            LEFT JOIN pg_opfamily sf ON sf.oid = a.amopsortfamily \
+            LEFT JOIN pg_namespace sfn ON sfn.oid = sf.opfnamespace \
+            WHERE a.amopfamily = $1",
+            AmOp::tlist(pgver).join(", "),
+        );
+
+        let rows = client
+            .query(&sql[..], &[&opfid])
+            .expect("Could not get pg_amop rows");
+
+        for row in &rows {
+            let amop = AmOp::from_row(row);
+            amops.insert(amop.amopkey.clone(), amop);
+        }
+
+        amops
+    }
+}
@@ -0,0 +1,44 @@
+/*----------------------------------------------------------------------------
+ * Author: Julien Rouhaud
+ * Copyright: Copyright (c) 2021-2026 : Julien Rouhaud - All rights reserved
+ *---------------------------------------------------------------------------*/
+use postgres::{Row, Transaction};
+use std::collections::BTreeMap;
+
+use crate::{compare::*, pgdiff::SchemaDiff, pgtype::*, proc_prototype, DbStruct};
+
+DbStruct! {
+    AmProc:amprockey:FamilyFunction {
+        amprockey: Text = ("a.amprocnum || ' (' || \
+            a.amproclefttype::regtype::text || ', ' || \
+            a.amprocrighttype::regtype::text || ')'"),
+        amprocnum: Smallint,
+        amproclefttype: Text = ("a.amproclefttype::regtype::text"),
+        amprocrighttype: Text = ("a.amprocrighttype::regtype::text"),
+        amproc: Text = (proc_prototype!("a.amproc")),
+    }
+}
+
+impl AmProc {
+    pub fn snapshot(client: &mut Transaction, opfid: u32, pgver: u32) -> BTreeMap<String, AmProc> {
+        let mut amprocs = BTreeMap::new();
+
+        let sql = format!(
+            "SELECT {} \
+            FROM pg_amproc a \
+            WHERE a.amprocfamily = $1",
+            AmProc::tlist(pgver).join(", "),
+        );
+
+        let rows = client
+            .query(&sql[..], &[&opfid])
+            .expect("Could not get pg_amproc rows");
+
+        for row in &rows {
+            let amproc = AmProc::from_row(row);
+            amprocs.insert(amproc.amprockey.clone(), amproc);
+        }
+
+        amprocs
+    }
+}
@@ -4,13 +4,14 @@
  *---------------------------------------------------------------------------*/
 use postgres::{Row, Transaction};
 
-use crate::{compare::*, pgdiff::SchemaDiff, pgtype::*, DbStruct};
+use crate::{compare::*, init_privs, pgdiff::SchemaDiff, pgtype::*, seclabels, DbStruct};
 
 DbStruct! {
     Attribute:attname:Attribute {
         attname: Name,
         atttype: Text = ("pg_catalog.format_type(a.atttypid, a.atttypmod)"),
-        attstattarget: Option<Integer> = ("attstattarget::int"),
+        // NULL means default since pg17, previously -1
+        attstattarget: Integer = ("coalesce(attstattarget::int, -1)"),
         attnum: Integer = ("(row_number() OVER(ORDER BY attnum))::int"),
         attndims: Integer = ("attndims::int"),
         attstorage: Char,
@@ -23,9 +24,11 @@ DbStruct! {
         attinhcount: Integer = ("attinhcount::int"),
         attcollation: Option<Name> = ("c.collname"),
         attacl: Option<Text> = ("attacl::text"),
+        initprivs: Option<Text> = (init_privs!("pg_class", "a.attrelid", "a.attnum")) {PG_9_6..},
         attoptions: Option<ClassOptions>,
-        attfdwoptions: Option<ClassOptions>,
+        attfdwoptions: Option<ClassOptions> {PG_9_2..},
         comment: Option<Text> = ("col_description(a.attrelid, a.attnum)"),
+        seclabels: Option<SecLabels> = (seclabels!("pg_class", "a.attrelid", "a.attnum")),
     }
 }
 
@@ -59,3 +62,40 @@ impl Attribute {
         atts
     }
 }
+
+// Raw physical layout of the relation's tuples, including dropped columns
+DbStruct! {
+    PhysicalAttribute:attnum:PhysicalAttribute {
+        attnum: Text = ("attnum::text"),
+        attisdropped: Bool,
+        attlen: Smallint,
+        attalign: Char,
+        attbyval: Bool,
+        attstorage: Char,
+    }
+}
+
+impl PhysicalAttribute {
+    pub fn snapshot(client: &mut Transaction, relid: u32, pgver: u32) -> Vec<PhysicalAttribute> {
+        let mut atts = Vec::new();
+
+        let sql = format!(
+            "SELECT {} \
+            FROM pg_attribute \
+            WHERE attnum > 0 \
+            AND attrelid = $1 \
+            ORDER BY attnum",
+            PhysicalAttribute::tlist(pgver).join(", "),
+        );
+
+        let rows = client
+            .query(&sql[..], &[&relid])
+            .expect("Could not get pg_attribute rows");
+
+        for row in &rows {
+            atts.push(PhysicalAttribute::from_row(row));
+        }
+
+        atts
+    }
+}
@@ -7,16 +7,22 @@ use std::collections::BTreeMap;
 
 use crate::{
     compare::*,
-    extension::pg_attribute::Attribute,
+    extension::pg_attribute::{Attribute, PhysicalAttribute},
     extension::pg_constraint::Constraint,
+    extension::pg_foreign_table::ForeignTable,
     extension::pg_index::Index,
+    extension::pg_inherits::PgInherits,
     extension::pg_policy::Policy,
     extension::pg_rewrite::Rewrite,
+    extension::pg_sequence::Sequence,
     extension::pg_statistic_ext::{ExtendedStatistic, PG_MIN_VER as EXT_STATS_MIN_VER},
     extension::pg_trigger::Trigger,
+    extension::pg_view::View,
+    extension::SnapshotOptions,
+    init_privs,
     pgdiff::SchemaDiff,
     pgtype::*,
-    CompareStruct, DbStruct,
+    seclabels, CompareStruct, DbStruct,
 };
 
 DbStruct! {
@@ -38,21 +44,37 @@ DbStruct! {
         relispartition: Bool {PG_10..},
         relpartkey: Text = ("pg_get_partkeydef(c.oid)") {PG_10..},
         relacl: Option<Text> = ("relacl::text"),
-        reloptions: Option<ClassOptions>,
+        initprivs: Option<Text> = (init_privs!("pg_class", "c.oid")) {PG_9_6..},
+        // The view options are compared with the view definition
+        reloptions: Option<ClassOptions> = ("CASE WHEN c.relkind = 'v' \
+            THEN (SELECT array_agg(o) FROM unnest(c.reloptions) o \
+                WHERE split_part(o, '=', 1) NOT IN ('check_option', \
+                    'security_barrier', 'security_invoker')) \
+            ELSE c.reloptions END"),
+        toast_reloptions: Option<ClassOptions> = ("(SELECT t.reloptions \
+            FROM pg_class t \
+            WHERE t.oid = c.reltoastrelid)"),
+        reltablespace: Option<Name> = ("ts.spcname"),
         relpartbound: Text = ("pg_get_expr(c.relpartbound, c.oid)") {PG_10..},
         comment: Option<Text> = ("obj_description(c.oid, 'pg_class')"),
+        seclabels: Option<SecLabels> = (seclabels!("pg_class", "c.oid")),
     }
 }
 
 CompareStruct! {
     Relation {
         attributes: Vec<Attribute>,
+        layout: Option<Vec<PhysicalAttribute>>,
         indexes: BTreeMap<String, Index>,
         stats: Option<BTreeMap<String, ExtendedStatistic>>,
         constraints: BTreeMap<String, Constraint>,
         rules: BTreeMap<String, Rewrite>,
         triggers: BTreeMap<String, Trigger>,
         policies: BTreeMap<String, Policy>,
+        foreign_table: Option<ForeignTable>,
+        view: Option<View>,
+        sequence: Option<Sequence>,
+        inheritance: PgInherits,
         class: PgClass,
     }
 }
@@ -62,11 +84,12 @@ impl Relation {
         client: &mut Transaction,
         oids: Vec<u32>,
         pgver: u32,
+        options: SnapshotOptions,
     ) -> BTreeMap<String, Relation> {
         let mut rels = BTreeMap::new();
 
         for oid in oids {
-            if let Some(r) = snap_one_class(client, oid, pgver) {
+            if let Some(r) = snap_one_class(client, oid, pgver, options) {
                 rels.insert(r.ident.clone(), r);
             }
         }
@@ -75,12 +98,18 @@ impl Relation {
     }
 }
 
-fn snap_one_class(client: &mut Transaction, oid: u32, pgver: u32) -> Option<Relation> {
+fn snap_one_class(
+    client: &mut Transaction,
+    oid: u32,
+    pgver: u32,
+    options: SnapshotOptions,
+) -> Option<Relation> {
     let sql = format!(
         "SELECT {} \
         FROM pg_class c \
         JOIN pg_roles r ON r.oid = c.relowner \
         LEFT JOIN pg_am am ON am.oid = c.relam \
+        LEFT JOIN pg_tablespace ts ON ts.oid = c.reltablespace \
         WHERE c.oid = $1",
         PgClass::tlist(pgver).join(", "),
     );
@@ -96,12 +125,26 @@ fn snap_one_class(client: &mut Transaction, oid: u32, pgver: u32) -> Option<Rela
     assert!(!class.relkind != 'i' as Char);
 
     let atts = Attribute::snapshot(client, oid, pgver);
+    let layout = match options.physical_layout {
+        true => Some(PhysicalAttribute::snapshot(client, oid, pgver)),
+        false => None,
+    };
     let indexes = Index::snapshot(client, oid, pgver);
 
     let stats = match pgver {
         EXT_STATS_MIN_VER..=PG_MAX => Some(ExtendedStatistic::snapshot(client, oid, pgver)),
         _ => None,
     };
+    let sequence = match class.relkind == 'S' as Char {
+        true => Some(Sequence::snapshot(
+            client,
+            oid,
+            &class.relname,
+            pgver,
+            options,
+        )),
+        false => None,
+    };
 
     let constraints = Constraint::snapshot_per_table(client, oid, pgver);
     let rules = Rewrite::snapshot(client, oid, pgver);
@@ -110,10 +153,20 @@ fn snap_one_class(client: &mut Transaction, oid: u32, pgver: u32) -> Option<Rela
         PG_9_5..=PG_MAX => Policy::snapshot(client, oid, pgver),
         _ => BTreeMap::new(),
     };
+    let foreign_table = match class.relkind == 'f' as Char {
+        true => Some(ForeignTable::snapshot(client, oid, pgver)),
+        false => None,
+    };
+    let inheritance = PgInherits::snapshot(client, oid, pgver);
+    let view = match class.relkind as u8 as char {
+        'v' | 'm' => Some(View::snapshot(client, oid, pgver)),
+        _ => None,
+    };
 
     Some(Relation {
         ident: class.relname.clone(),
         attributes: atts,
+        layout,
         stats,
         indexes,
         constraints,
@@ -121,5 +174,9 @@ fn snap_one_class(client: &mut Transaction, oid: u32, pgver: u32) -> Option<Rela
         triggers,
         class,
         policies,
+        foreign_table,
+        view,
+        sequence,
+        inheritance,
     })
 }
@@ -0,0 +1,63 @@
+/*----------------------------------------------------------------------------
+ * Author: Julien Rouhaud
+ * Copyright: Copyright (c) 2021-2026 : Julien Rouhaud - All rights reserved
+ *---------------------------------------------------------------------------*/
+use postgres::{Row, Transaction};
+use std::collections::BTreeMap;
+
+use crate::{compare::*, elog::*, pgdiff::SchemaDiff, pgtype::*, DbStruct};
+
+DbStruct! {
+    Collation:collname:Collation {
+        collname: Text = ("n.nspname || '.' || c.collname"),
+        collowner: Name = ("r.rolname"),
+        collprovider: Char {PG_10..},
+        collisdeterministic: Bool {PG_12..},
+        collencoding: Name = ("pg_encoding_to_char(c.collencoding)"),
+        collcollate: Option<Text> = ("c.collcollate::text"),
+        collctype: Option<Text> = ("c.collctype::text"),
+        colliculocale: Option<Text> {PG_15..PG_17},
+        colllocale: Option<Text> {PG_17..},
+        collicurules: Option<Text> {PG_16..},
+        collversion: Option<Text> {PG_10..},
+        comment: Option<Text> = ("obj_description(c.oid, 'pg_collation')"),
+    }
+}
+
+impl Collation {
+    pub fn snapshot(
+        client: &mut Transaction,
+        oids: Vec<u32>,
+        pgver: u32,
+    ) -> BTreeMap<String, Collation> {
+        let mut colls = BTreeMap::new();
+
+        for oid in oids {
+            let coll = snap_one_collation(client, oid, pgver);
+            colls.insert(coll.collname.clone(), coll);
+        }
+
+        colls
+    }
+}
+
+pub fn snap_one_collation(client: &mut Transaction, oid: u32, pgver: u32) -> Collation {
+    let sql = format!(
+        "SELECT {} \
+        FROM pg_collation c \
+        JOIN pg_namespace n ON n.oid = c.collnamespace \
+        JOIN pg_roles r ON r.oid = c.collowner \
+        WHERE c.oid = $1",
+        Collation::tlist(pgver).join(", "),
+    );
+
+    let row = match client.query_one(&sql[..], &[&oid]) {
+        Ok(r) => r,
+        Err(e) => {
+            elog(ERROR, &format!("{}", e));
+            panic!();
+        }
+    };
+
+    Collation::from_row(&row)
+}
@@ -11,6 +11,9 @@ DbStruct! {
     Constraint:conname:Constraint {
         conname: Name = ("nspname || '.' || conname"),
         condef: Text = ("pg_get_constraintdef(c.oid)"),
+        convalidated: Bool,
+        conenforced: Bool {PG_18..},
+        conperiod: Bool {PG_18..},
         comment: Option<Text> = ("obj_description(c.oid, 'pg_constraint')"),
     }
 }
@@ -42,4 +45,31 @@ impl Constraint {
 
         cons
     }
+
+    pub fn snapshot_per_type(
+        client: &mut Transaction,
+        typid: u32,
+        pgver: u32,
+    ) -> BTreeMap<String, Constraint> {
+        let mut cons = BTreeMap::new();
+
+        let sql = format!(
+            "SELECT {} \
+            FROM pg_constraint c \
+            JOIN pg_namespace n ON n.oid = c.connamespace \
+            WHERE contypid = $1",
+            Constraint::tlist(pgver).join(", "),
+        );
+
+        let rows = client
+            .query(&sql[..], &[&typid])
+            .expect("Could not get pg_constraint rows");
+
+        for row in &rows {
+            let con = Constraint::from_row(row);
+            cons.insert(con.conname.clone(), con);
+        }
+
+        cons
+    }
 }
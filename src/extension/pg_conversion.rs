@@ -0,0 +1,58 @@
+/*----------------------------------------------------------------------------
+ * Author: Julien Rouhaud
+ * Copyright: Copyright (c) 2021-2026 : Julien Rouhaud - All rights reserved
+ *---------------------------------------------------------------------------*/
+use postgres::{Row, Transaction};
+use std::collections::BTreeMap;
+
+use crate::{compare::*, elog::*, pgdiff::SchemaDiff, pgtype::*, proc_prototype, DbStruct};
+
+DbStruct! {
+    Conversion:conname:Conversion {
+        conname: Text = ("n.nspname || '.' || c.conname"),
+        conowner: Name = ("r.rolname"),
+        conforencoding: Name = ("pg_encoding_to_char(c.conforencoding)"),
+        contoencoding: Name = ("pg_encoding_to_char(c.contoencoding)"),
+        conproc: Text = (proc_prototype!("c.conproc")),
+        condefault: Bool,
+        comment: Option<Text> = ("obj_description(c.oid, 'pg_conversion')"),
+    }
+}
+
+impl Conversion {
+    pub fn snapshot(
+        client: &mut Transaction,
+        oids: Vec<u32>,
+        pgver: u32,
+    ) -> BTreeMap<String, Conversion> {
+        let mut convs = BTreeMap::new();
+
+        for oid in oids {
+            let conv = snap_one_conversion(client, oid, pgver);
+            convs.insert(conv.conname.clone(), conv);
+        }
+
+        convs
+    }
+}
+
+pub fn snap_one_conversion(client: &mut Transaction, oid: u32, pgver: u32) -> Conversion {
+    let sql = format!(
+        "SELECT {} \
+        FROM pg_conversion c \
+        JOIN pg_namespace n ON n.oid = c.connamespace \
+        JOIN pg_roles r ON r.oid = c.conowner \
+        WHERE c.oid = $1",
+        Conversion::tlist(pgver).join(", "),
+    );
+
+    let row = match client.query_one(&sql[..], &[&oid]) {
+        Ok(r) => r,
+        Err(e) => {
+            elog(ERROR, &format!("{}", e));
+            panic!();
+        }
+    };
+
+    Conversion::from_row(&row)
+}
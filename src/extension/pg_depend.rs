@@ -0,0 +1,156 @@
+/*----------------------------------------------------------------------------
+ * Author: Julien Rouhaud
+ * Copyright: Copyright (c) 2021-2026 : Julien Rouhaud - All rights reserved
+ *---------------------------------------------------------------------------*/
+use postgres::{Row, Transaction};
+use std::collections::BTreeMap;
+
+use crate::{compare::*, pgdiff::SchemaDiff, pgtype::*, CompareStruct, DbStruct};
+
+// Generic representation of an extension member whose catalog isn't
+// explicitly handled.  Only its identity can be compared.
+DbStruct! {
+    UnhandledObject:identity:Object {
+        identity: Text = ("pg_describe_object(o.classid, o.objid, 0)"),
+        objtype: Option<Text> = ("(pg_identify_object_as_address(o.classid, \
+            o.objid, 0)).type") {PG_9_5..},
+        object_names: Option<Text> = ("(pg_identify_object_as_address(o.classid, \
+            o.objid, 0)).object_names::text") {PG_9_5..},
+        object_args: Option<Text> = ("(pg_identify_object_as_address(o.classid, \
+            o.objid, 0)).object_args::text") {PG_9_5..},
+    }
+}
+
+impl UnhandledObject {
+    pub fn snapshot(
+        client: &mut Transaction,
+        classid: &str,
+        oids: Vec<u32>,
+        pgver: u32,
+        objs: &mut BTreeMap<String, UnhandledObject>,
+    ) {
+        let sql = format!(
+            "SELECT {} \
+            FROM (SELECT $1::text::regclass::oid AS classid, $2::oid AS objid) o",
+            UnhandledObject::tlist(pgver).join(", "),
+        );
+
+        for oid in oids {
+            let row = client
+                .query_one(&sql[..], &[&classid, &oid])
+                .expect("Could not identify object");
+
+            let obj = UnhandledObject::from_row(&row);
+            objs.insert(obj.identity.clone(), obj);
+        }
+    }
+}
+
+DbStruct! {
+    Dependency:refobj:Dependency {
+        refobj: Text = ("pg_describe_object(d.classid, d.objid, d.objsubid) || ' -> ' || \
+            pg_describe_object(d.refclassid, d.refobjid, d.refobjsubid)"),
+        deptype: Char = ("d.deptype"),
+    }
+}
+
+CompareStruct! {
+    ObjectDependencies {
+        dependencies: BTreeMap<String, Dependency>,
+    }
+}
+
+// Outgoing pg_depend edges of all extension members, including the ones of
+// their internal or automatic sub-objects (column defaults, view rules,
+// constraints...), keyed by the member identity.  Sub-objects named after an
+// oid, which is different for every installation, are ignored: TOAST tables
+// (and their indexes) and internal triggers, like the ones implementing
+// foreign keys.  Those are entirely derived from their owning object anyway.
+#[derive(Debug)]
+pub struct Dependencies {
+    objects: BTreeMap<String, ObjectDependencies>,
+}
+
+impl Dependencies {
+    pub fn snapshot(client: &mut Transaction, extname: &str, pgver: u32) -> Self {
+        let mut objects = BTreeMap::new();
+
+        let sql = format!(
+            "WITH RECURSIVE members AS ( \
+                SELECT d.classid, d.objid, \
+                    d.classid AS rootclassid, d.objid AS rootobjid \
+                FROM pg_depend d \
+                JOIN pg_extension e ON e.oid = d.refobjid \
+                WHERE d.refclassid = 'pg_extension'::regclass \
+                AND d.deptype = 'e' \
+                AND e.extname = $1 \
+                UNION \
+                SELECT d.classid, d.objid, m.rootclassid, m.rootobjid \
+                FROM members m \
+                JOIN pg_depend d ON d.refclassid = m.classid \
+                    AND d.refobjid = m.objid \
+                WHERE d.deptype IN ('a', 'i') \
+                AND NOT EXISTS (SELECT 1 FROM pg_class c \
+                    WHERE d.classid = 'pg_class'::regclass \
+                    AND c.oid = d.objid AND c.relkind = 't') \
+                AND NOT EXISTS (SELECT 1 FROM pg_trigger t \
+                    WHERE d.classid = 'pg_trigger'::regclass \
+                    AND t.oid = d.objid AND t.tgisinternal) \
+            ) \
+            SELECT DISTINCT pg_describe_object(m.rootclassid, m.rootobjid, 0) AS __member, {} \
+            FROM members m \
+            JOIN pg_depend d ON d.classid = m.classid AND d.objid = m.objid \
+            WHERE d.refclassid <> 'pg_extension'::regclass",
+            Dependency::tlist(pgver).join(", "),
+        );
+
+        let rows = client
+            .query(&sql[..], &[&extname])
+            .expect("Could not get pg_depend rows");
+
+        for row in &rows {
+            let member: String = row.get("__member");
+            let dep = Dependency::from_row(row);
+
+            objects
+                .entry(member.clone())
+                .or_insert_with(|| ObjectDependencies {
+                    ident: member,
+                    dependencies: BTreeMap::new(),
+                })
+                .dependencies
+                .insert(dep.refobj.clone(), dep);
+        }
+
+        Dependencies { objects }
+    }
+}
+
+impl<'a> Compare<'a> for Dependencies {
+    fn compare(&'a self, other: &'a Self) -> Option<SchemaDiff<'a>> {
+        let mut nb = 0;
+        let mut diffs = vec![];
+
+        // Missing objects are already reported by the rest of the snapshot, so
+        // only compare the dependencies of objects existing on both sides.
+        for (k, v) in self.objects.iter() {
+            if let Some(o) = other.objects.get(k) {
+                nb += 1;
+                if let Some(d) = v.compare(o) {
+                    diffs.push(d);
+                }
+            }
+        }
+
+        match diffs.len() {
+            0 => None,
+            _ => Some(SchemaDiff::HashMapDiff(
+                nb,
+                nb,
+                ObjectDependencies::typname(),
+                vec![],
+                diffs,
+            )),
+        }
+    }
+}
@@ -5,7 +5,9 @@
 use postgres::{Row, Transaction};
 use std::collections::BTreeMap;
 
-use crate::{compare::*, elog::*, pgdiff::SchemaDiff, pgtype::*, proc_prototype, DbStruct};
+use crate::{
+    compare::*, elog::*, pgdiff::SchemaDiff, pgtype::*, proc_prototype, seclabels, DbStruct,
+};
 
 DbStruct! {
     EventTrigger:evtname:EventTrigger {
@@ -16,6 +18,7 @@ DbStruct! {
         evtenabled: Char,
         evttags: Option<List>,
         comment: Option<Text> = ("obj_description(t.oid, 'pg_event_trigger')"),
+        seclabels: Option<SecLabels> = (seclabels!("pg_event_trigger", "t.oid")),
     }
 }
 
@@ -0,0 +1,41 @@
+/*----------------------------------------------------------------------------
+ * Author: Julien Rouhaud
+ * Copyright: Copyright (c) 2021-2026 : Julien Rouhaud - All rights reserved
+ *---------------------------------------------------------------------------*/
+use postgres::{Row, Transaction};
+
+use crate::{compare::*, pgdiff::SchemaDiff, pgtype::*, DbStruct};
+
+DbStruct! {
+    PgExtension:extname:Extension {
+        extname: Name,
+        extowner: Name = ("r.rolname"),
+        extnamespace: Name = ("n.nspname"),
+        extrelocatable: Bool,
+        extversion: Text,
+        comment: Option<Text> = ("obj_description(e.oid, 'pg_extension')"),
+    }
+}
+
+impl PgExtension {
+    pub fn snapshot(client: &mut Transaction, extname: &str, pgver: u32) -> Self {
+        let sql = format!(
+            "SELECT {} \
+            FROM pg_extension e \
+            JOIN pg_roles r ON r.oid = e.extowner \
+            JOIN pg_namespace n ON n.oid = e.extnamespace \
+            WHERE e.extname = $1",
+            PgExtension::tlist(pgver).join(", "),
+        );
+
+        let row = client
+            .query_one(&sql[..], &[&extname])
+            .expect("Could not get pg_extension row");
+
+        PgExtension::from_row(&row)
+    }
+
+    pub fn extversion(&self) -> &str {
+        &self.extversion
+    }
+}
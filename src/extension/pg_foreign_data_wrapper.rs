@@ -5,7 +5,9 @@
 use postgres::{Row, Transaction};
 use std::collections::BTreeMap;
 
-use crate::{compare::*, elog::*, pgdiff::SchemaDiff, pgtype::*, proc_prototype, DbStruct};
+use crate::{
+    compare::*, elog::*, init_privs, pgdiff::SchemaDiff, pgtype::*, proc_prototype, DbStruct,
+};
 
 DbStruct! {
     ForeignDataWrapper:fdwname:ForeignDataWrapper {
@@ -14,6 +16,7 @@ DbStruct! {
         fdwhandler: Option<Text> = (proc_prototype!("fdwhandler")),
         fdwvalidator: Option<Text> = (proc_prototype!("fdwvalidator")),
         fdwacl: Option<Text> = ("fdwacl::text"),
+        initprivs: Option<Text> = (init_privs!("pg_foreign_data_wrapper", "fdw.oid")) {PG_9_6..},
         fdwoptions: Option<ClassOptions>,
         comment: Option<Text> = ("d.description"),
     }
@@ -0,0 +1,76 @@
+/*----------------------------------------------------------------------------
+ * Author: Julien Rouhaud
+ * Copyright: Copyright (c) 2021-2026 : Julien Rouhaud - All rights reserved
+ *---------------------------------------------------------------------------*/
+use postgres::{Row, Transaction};
+use std::collections::BTreeMap;
+
+use crate::{
+    compare::*, elog::*, extension::pg_user_mapping::UserMapping, init_privs, pgdiff::SchemaDiff,
+    pgtype::*, CompareStruct, DbStruct,
+};
+
+DbStruct! {
+    PgForeignServer:srvname:ForeignServer {
+        srvname: Text,
+        srvfdw: Name = ("fdw.fdwname"),
+        srvowner: Name = ("r.rolname"),
+        srvtype: Option<Text>,
+        srvversion: Option<Text>,
+        srvacl: Option<Text> = ("srv.srvacl::text"),
+        initprivs: Option<Text> = (init_privs!("pg_foreign_server", "srv.oid")) {PG_9_6..},
+        srvoptions: Option<ClassOptions>,
+        comment: Option<Text> = ("obj_description(srv.oid, 'pg_foreign_server')"),
+    }
+}
+
+CompareStruct! {
+    ForeignServer {
+        server: PgForeignServer,
+        user_mappings: BTreeMap<String, UserMapping>,
+    }
+}
+
+impl ForeignServer {
+    pub fn snapshot(
+        client: &mut Transaction,
+        oids: Vec<u32>,
+        pgver: u32,
+    ) -> BTreeMap<String, ForeignServer> {
+        let mut srvs = BTreeMap::new();
+
+        for oid in oids {
+            let srv = snap_one_srv(client, oid, pgver);
+            srvs.insert(srv.ident.clone(), srv);
+        }
+
+        srvs
+    }
+}
+
+pub fn snap_one_srv(client: &mut Transaction, oid: u32, pgver: u32) -> ForeignServer {
+    let sql = format!(
+        "SELECT {} \
+        FROM pg_foreign_server srv \
+        JOIN pg_foreign_data_wrapper fdw ON fdw.oid = srv.srvfdw \
+        JOIN pg_roles r ON r.oid = srv.srvowner \
+        WHERE srv.oid = $1",
+        PgForeignServer::tlist(pgver).join(", "),
+    );
+
+    let row = match client.query_one(&sql[..], &[&oid]) {
+        Ok(r) => r,
+        Err(e) => {
+            elog(ERROR, &format!("{}", e));
+            panic!();
+        }
+    };
+
+    let server = PgForeignServer::from_row(&row);
+
+    ForeignServer {
+        ident: server.srvname.clone(),
+        server,
+        user_mappings: UserMapping::snapshot(client, oid, pgver),
+    }
+}
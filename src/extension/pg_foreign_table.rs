@@ -0,0 +1,33 @@
+/*----------------------------------------------------------------------------
+ * Author: Julien Rouhaud
+ * Copyright: Copyright (c) 2021-2026 : Julien Rouhaud - All rights reserved
+ *---------------------------------------------------------------------------*/
+use postgres::{Row, Transaction};
+
+use crate::{compare::*, pgdiff::SchemaDiff, pgtype::*, DbStruct};
+
+DbStruct! {
+    ForeignTable:ftrelid:ForeignTable {
+        ftrelid: Text = ("ft.ftrelid::regclass::text"),
+        ftserver: Name = ("srv.srvname"),
+        ftoptions: Option<ClassOptions> = ("ft.ftoptions"),
+    }
+}
+
+impl ForeignTable {
+    pub fn snapshot(client: &mut Transaction, relid: u32, pgver: u32) -> ForeignTable {
+        let sql = format!(
+            "SELECT {} \
+            FROM pg_foreign_table ft \
+            JOIN pg_foreign_server srv ON srv.oid = ft.ftserver \
+            WHERE ft.ftrelid = $1",
+            ForeignTable::tlist(pgver).join(", "),
+        );
+
+        let row = client
+            .query_one(&sql[..], &[&relid])
+            .expect("Could not get pg_foreign_table row");
+
+        ForeignTable::from_row(&row)
+    }
+}
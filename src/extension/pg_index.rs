@@ -5,13 +5,35 @@
 use postgres::{Row, Transaction};
 use std::collections::BTreeMap;
 
-use crate::{compare::*, pgdiff::SchemaDiff, pgtype::*, DbStruct};
+use crate::{compare::*, pgdiff::SchemaDiff, pgtype::*, CompareStruct, DbStruct};
 
 DbStruct! {
-    Index:indname:Index {
-        indname: Name = ("indexrelid::regclass::text"),
-        inddef: Text = ("pg_get_indexdef(indexrelid)"),
-        comment: Option<Text> = ("obj_description(indexrelid, 'pg_class')"),
+    PgIndex:indname:Index {
+        indname: Name = ("i.indexrelid::regclass::text"),
+        inddef: Text = ("pg_get_indexdef(i.indexrelid)"),
+        indisvalid: Bool,
+        indisready: Bool,
+        indisclustered: Bool,
+        indisreplident: Bool {PG_9_4..},
+        indnullsnotdistinct: Bool {PG_15..},
+        reloptions: Option<ClassOptions> = ("c.reloptions"),
+        reltablespace: Option<Name> = ("ts.spcname"),
+        comment: Option<Text> = ("obj_description(i.indexrelid, 'pg_class')"),
+    }
+}
+
+DbStruct! {
+    IndexColumn:attname:IndexColumn {
+        attname: Name,
+        attstattarget: Option<Integer> = ("attstattarget::int"),
+        comment: Option<Text> = ("col_description(attrelid, attnum)"),
+    }
+}
+
+CompareStruct! {
+    Index {
+        index: PgIndex,
+        columns: Vec<IndexColumn>,
     }
 }
 
@@ -20,11 +42,13 @@ impl Index {
         let mut indexes = BTreeMap::new();
 
         let sql = format!(
-            "SELECT {} \
-            FROM pg_index \
-            WHERE indrelid = $1 \
-            ORDER BY indexrelid::regclass::text",
-            Index::tlist(pgver).join(", "),
+            "SELECT {}, i.indexrelid AS __indexrelid \
+            FROM pg_index i \
+            JOIN pg_class c ON c.oid = i.indexrelid \
+            LEFT JOIN pg_tablespace ts ON ts.oid = c.reltablespace \
+            WHERE i.indrelid = $1 \
+            ORDER BY i.indexrelid::regclass::text",
+            PgIndex::tlist(pgver).join(", "),
         );
 
         let rows = client
@@ -32,10 +56,44 @@ impl Index {
             .expect("Could net get pg_index rows");
 
         for row in &rows {
-            let ind = Index::from_row(row);
-            indexes.insert(ind.indname.clone(), ind);
+            let indexrelid: u32 = row.get("__indexrelid");
+            let ind = PgIndex::from_row(row);
+
+            indexes.insert(
+                ind.indname.clone(),
+                Index {
+                    ident: ind.indname.clone(),
+                    index: ind,
+                    columns: IndexColumn::snapshot(client, indexrelid, pgver),
+                },
+            );
         }
 
         indexes
     }
 }
+
+impl IndexColumn {
+    pub fn snapshot(client: &mut Transaction, indexrelid: u32, pgver: u32) -> Vec<IndexColumn> {
+        let mut cols = Vec::new();
+
+        let sql = format!(
+            "SELECT {} \
+            FROM pg_attribute \
+            WHERE attnum > 0 \
+            AND attrelid = $1 \
+            ORDER BY attnum",
+            IndexColumn::tlist(pgver).join(", "),
+        );
+
+        let rows = client
+            .query(&sql[..], &[&indexrelid])
+            .expect("Could not get index pg_attribute rows");
+
+        for row in &rows {
+            cols.push(IndexColumn::from_row(row));
+        }
+
+        cols
+    }
+}
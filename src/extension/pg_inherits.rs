@@ -0,0 +1,41 @@
+/*----------------------------------------------------------------------------
+ * Author: Julien Rouhaud
+ * Copyright: Copyright (c) 2021-2026 : Julien Rouhaud - All rights reserved
+ *---------------------------------------------------------------------------*/
+use postgres::{Row, Transaction};
+
+use crate::{compare::*, pgdiff::SchemaDiff, pgtype::*, DbStruct};
+
+DbStruct! {
+    PgInherits:relname:Inheritance {
+        relname: Text = ("c.oid::regclass::text"),
+        inhparents: Vec<Text> = ("ARRAY(SELECT i.inhparent::regclass::text \
+            FROM pg_inherits i \
+            WHERE i.inhrelid = c.oid \
+            ORDER BY i.inhseqno)"),
+        inhchildren: Vec<Text> = ("ARRAY(SELECT i.inhrelid::regclass::text \
+            FROM pg_inherits i \
+            WHERE i.inhparent = c.oid \
+            ORDER BY 1)"),
+        partdefid: Option<Text> = ("(SELECT NULLIF(p.partdefid, 0)::regclass::text \
+            FROM pg_partitioned_table p \
+            WHERE p.partrelid = c.oid)") {PG_11..},
+    }
+}
+
+impl PgInherits {
+    pub fn snapshot(client: &mut Transaction, relid: u32, pgver: u32) -> PgInherits {
+        let sql = format!(
+            "SELECT {} \
+            FROM pg_class c \
+            WHERE c.oid = $1",
+            PgInherits::tlist(pgver).join(", "),
+        );
+
+        let row = client
+            .query_one(&sql[..], &[&relid])
+            .expect("Could not get pg_inherits rows");
+
+        PgInherits::from_row(&row)
+    }
+}
@@ -5,14 +5,16 @@
 use postgres::{Row, Transaction};
 use std::collections::BTreeMap;
 
-use crate::{compare::*, elog::*, pgdiff::SchemaDiff, pgtype::*, DbStruct};
+use crate::{compare::*, elog::*, init_privs, pgdiff::SchemaDiff, pgtype::*, seclabels, DbStruct};
 
 DbStruct! {
     Namespace:nspname:Namespace {
         nspname: Text,
         nspowner: Name = ("r.rolname"),
         nspacl: Option<Text> = ("nspacl::text"),
+        initprivs: Option<Text> = (init_privs!("pg_namespace", "nsp.oid")) {PG_9_6..},
         comment: Option<Text> = ("obj_description(nsp.oid, 'pg_namespace')"),
+        seclabels: Option<SecLabels> = (seclabels!("pg_namespace", "nsp.oid")),
     }
 }
 
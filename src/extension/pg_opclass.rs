@@ -16,6 +16,7 @@ DbStruct! {
         opcintype: Name = ("opc.opcintype::regtype::text"),
         opcdefault: Bool,
         opckeytype: Option<Name> = ("opc.opckeytype::regtype::text"),
+        comment: Option<Text> = ("obj_description(opc.oid, 'pg_opclass')"),
     }
 }
 
@@ -5,13 +5,25 @@
 use postgres::{Row, Transaction};
 use std::collections::BTreeMap;
 
-use crate::{compare::*, elog::*, pgdiff::SchemaDiff, pgtype::*, DbStruct};
+use crate::{
+    compare::*, elog::*, extension::pg_amop::AmOp, extension::pg_amproc::AmProc,
+    pgdiff::SchemaDiff, pgtype::*, CompareStruct, DbStruct,
+};
 
 DbStruct! {
-    OpFamily:opfname:OpFamily {
+    PgOpFamily:opfname:OpFamily {
         opfname: Text = ("n.nspname || '.' || opf.opfname || ' USING ' || am.amname"),
         opfmethod: Name = ("am.amname"),
         opfowner: Name = ("r.rolname"),
+        comment: Option<Text> = ("obj_description(opf.oid, 'pg_opfamily')"),
+    }
+}
+
+CompareStruct! {
+    OpFamily {
+        family: PgOpFamily,
+        operators: BTreeMap<String, AmOp>,
+        functions: BTreeMap<String, AmProc>,
     }
 }
 
@@ -25,7 +37,7 @@ impl OpFamily {
 
         for oid in oids {
             let opf = snap_one_opf(client, oid, pgver);
-            opfs.insert(opf.opfname.clone(), opf);
+            opfs.insert(opf.ident.clone(), opf);
         }
 
         opfs
@@ -40,7 +52,7 @@ pub fn snap_one_opf(client: &mut Transaction, oid: u32, pgver: u32) -> OpFamily
         JOIN pg_am am ON am.oid = opf.opfmethod \
         JOIN pg_roles r ON r.oid = opf.opfowner \
         WHERE opf.oid = $1",
-        OpFamily::tlist(pgver).join(", "),
+        PgOpFamily::tlist(pgver).join(", "),
     );
 
     let row = match client.query_one(&sql[..], &[&oid]) {
@@ -51,5 +63,12 @@ pub fn snap_one_opf(client: &mut Transaction, oid: u32, pgver: u32) -> OpFamily
         }
     };
 
-    OpFamily::from_row(&row)
+    let family = PgOpFamily::from_row(&row);
+
+    OpFamily {
+        ident: family.opfname.clone(),
+        family,
+        operators: AmOp::snapshot(client, oid, pgver),
+        functions: AmProc::snapshot(client, oid, pgver),
+    }
 }
@@ -2,8 +2,8 @@ use postgres::{Row, Transaction};
 use std::collections::BTreeMap;
 
 use crate::{
-    compare::*, elog::*, extension::pg_aggregate::Aggregate, pgdiff::SchemaDiff, pgtype::*,
-    proc_prototype, CompareStruct, DbStruct,
+    compare::*, elog::*, extension::pg_aggregate::Aggregate, init_privs, pgdiff::SchemaDiff,
+    pgtype::*, proc_prototype, seclabels, CompareStruct, DbStruct,
 };
 
 DbStruct! {
@@ -16,16 +16,22 @@ DbStruct! {
         prosupport: Text = (proc_prototype!("p.prosupport")) {PG_12..},
         prokind: Char {PG_11..},
         prosecdef: Bool,
-        proleakproof: Bool,
+        proleakproof: Bool {PG_9_2..},
         proisstrict: Bool,
         provolatile: Char,
         proparallel: Char {PG_9_6..},
         prorettype: Option<Text> = ("pg_get_function_result(p.oid)"),
+        // Empty for SQL-standard function bodies, which are stored in
+        // prosqlbody instead, either as a RETURN or a BEGIN ATOMIC block.
         prosrc: Text,
-        prosqlbody: Text = ("pg_get_function_sqlbody(p.oid)") {PG_14..},
+        prosqlbody: Option<Text> = ("pg_get_function_sqlbody(p.oid)") {PG_14..},
+        protrftypes: Option<List> = ("(SELECT array_agg(t::regtype::text) \
+            FROM unnest(p.protrftypes) t)") {PG_9_5..},
         proconfig: Option<ClassOptions>,
         proacl: Option<Text> = ("proacl::text"),
+        initprivs: Option<Text> = (init_privs!("pg_proc", "p.oid")) {PG_9_6..},
         comment: Option<Text> = ("obj_description(p.oid, 'pg_proc')"),
+        seclabels: Option<SecLabels> = (seclabels!("pg_proc", "p.oid")),
         probin: Option<Text>,
     }
 }
@@ -37,9 +37,6 @@ impl Range {
             }
         };
 
-        match row {
-            Some(r) => Some(Range::from_row(&r)),
-            None => None,
-        }
+        row.map(|r| Range::from_row(&r))
     }
 }
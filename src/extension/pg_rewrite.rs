@@ -16,10 +16,13 @@ impl Rewrite {
     pub fn snapshot(client: &mut Transaction, relid: u32, pgver: u32) -> BTreeMap<String, Rewrite> {
         let mut rewrites = BTreeMap::new();
 
+        // The _RETURN rule of views and materialized views is compared using
+        // the view definition.
         let sql = format!(
             "SELECT {} \
             FROM pg_rewrite \
-            WHERE ev_class = $1",
+            WHERE ev_class = $1 \
+            AND rulename <> '_RETURN'",
             Rewrite::tlist(pgver).join(", "),
         );
 
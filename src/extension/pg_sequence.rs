@@ -0,0 +1,112 @@
+/*----------------------------------------------------------------------------
+ * Author: Julien Rouhaud
+ * Copyright: Copyright (c) 2021-2026 : Julien Rouhaud - All rights reserved
+ *---------------------------------------------------------------------------*/
+use postgres::{Row, Transaction};
+
+use crate::{
+    compare::*, extension::SnapshotOptions, pgdiff::SchemaDiff, pgtype::*, CompareStruct, DbStruct,
+};
+
+DbStruct! {
+    PgSequence:seqrelid:Sequence {
+        seqrelid: Text = ("c.oid::regclass::text"),
+        seqtypid: Text = ("s.seqtypid::regtype::text") {PG_10..},
+        seqstart: Bigint = ("s.seqstart"),
+        seqincrement: Bigint = ("s.seqincrement"),
+        seqmax: Bigint = ("s.seqmax"),
+        seqmin: Bigint = ("s.seqmin"),
+        seqcache: Bigint = ("s.seqcache"),
+        seqcycle: Bool = ("s.seqcycle"),
+        // Column owning the sequence, either with OWNED BY (and serial
+        // columns) or as an identity column.
+        owned_by: Option<Text> = ("(SELECT d.refobjid::regclass::text || '.' || \
+            quote_ident(a.attname) \
+            FROM pg_depend d \
+            JOIN pg_attribute a ON a.attrelid = d.refobjid \
+                AND a.attnum = d.refobjsubid \
+            WHERE d.classid = 'pg_class'::regclass \
+            AND d.objid = c.oid \
+            AND d.refclassid = 'pg_class'::regclass \
+            AND d.refobjsubid > 0 \
+            AND d.deptype IN ('a', 'i'))"),
+    }
+}
+
+DbStruct! {
+    SequenceValue:seqrelid:SequenceValue {
+        seqrelid: Text = ("c.oid::regclass::text"),
+        last_value: Bigint = ("l.last_value"),
+        is_called: Bool = ("l.is_called"),
+    }
+}
+
+CompareStruct! {
+    Sequence {
+        seq: PgSequence,
+        value: Option<SequenceValue>,
+    }
+}
+
+impl Sequence {
+    pub fn snapshot(
+        client: &mut Transaction,
+        relid: u32,
+        relname: &str,
+        pgver: u32,
+        options: SnapshotOptions,
+    ) -> Sequence {
+        // Before pg10, the sequence parameters are only stored in the
+        // sequence relation itself.
+        let from = match pgver {
+            PG_10..=PG_MAX => String::from("pg_sequence s JOIN pg_class c ON c.oid = s.seqrelid"),
+            _ => format!(
+                "(SELECT start_value AS seqstart, increment_by AS seqincrement, \
+                max_value AS seqmax, min_value AS seqmin, \
+                cache_value AS seqcache, is_cycled AS seqcycle \
+                FROM {}) s \
+                JOIN pg_class c ON true",
+                relname
+            ),
+        };
+
+        let sql = format!(
+            "SELECT {} \
+            FROM {} \
+            WHERE c.oid = $1",
+            PgSequence::tlist(pgver).join(", "),
+            from,
+        );
+
+        let row = client
+            .query_one(&sql[..], &[&relid])
+            .expect("Could not get sequence parameters");
+
+        let seq = PgSequence::from_row(&row);
+
+        let value = match options.sequence_values {
+            true => {
+                let sql = format!(
+                    "SELECT {} \
+                    FROM pg_class c, {} l \
+                    WHERE c.oid = $1",
+                    SequenceValue::tlist(pgver).join(", "),
+                    relname,
+                );
+
+                let row = client
+                    .query_one(&sql[..], &[&relid])
+                    .expect("Could not get sequence state");
+
+                Some(SequenceValue::from_row(&row))
+            }
+            false => None,
+        };
+
+        Sequence {
+            ident: seq.seqrelid.clone(),
+            seq,
+            value,
+        }
+    }
+}
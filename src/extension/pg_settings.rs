@@ -0,0 +1,205 @@
+/*----------------------------------------------------------------------------
+ * Author: Julien Rouhaud
+ * Copyright: Copyright (c) 2021-2026 : Julien Rouhaud - All rights reserved
+ *---------------------------------------------------------------------------*/
+use postgres::{Client, Row, Transaction};
+use std::collections::BTreeMap;
+
+use crate::{compare::*, elog::*, pgdiff::SchemaDiff, pgtype::*, DbStruct};
+
+DbStruct! {
+    Setting:name:Setting {
+        name: Text,
+        vartype: Text,
+        context: Text,
+        unit: Option<Text>,
+        boot_val: Option<Text>,
+        min_val: Option<Text>,
+        max_val: Option<Text>,
+        enumvals: Option<List>,
+        short_desc: Option<Text>,
+    }
+}
+
+impl Setting {
+    // Libraries used by the extension's C functions.
+    fn libraries(client: &mut Transaction, extname: &str) -> Vec<String> {
+        let rows = client
+            .query(
+                "SELECT DISTINCT p.probin \
+            FROM pg_depend d \
+            JOIN pg_extension e ON e.oid = d.refobjid \
+            JOIN pg_proc p ON p.oid = d.objid \
+            WHERE d.refclassid = 'pg_extension'::regclass \
+            AND d.classid = 'pg_proc'::regclass \
+            AND d.deptype = 'e' \
+            AND e.extname = $1 \
+            AND p.probin IS NOT NULL \
+            ORDER BY 1",
+                &[&extname],
+            )
+            .expect("Could not get the extension libraries");
+
+        rows.iter().map(|r| r.get(0)).collect()
+    }
+
+    // Custom GUCs defined in the extension's prefix.  Placeholders aren't
+    // visible in pg_settings, so only GUCs really defined by the extension's
+    // library are retrieved.
+    fn snapshot(session: &mut Client, extname: &str, pgver: u32) -> BTreeMap<String, Self> {
+        let mut settings = BTreeMap::new();
+
+        let sql = format!(
+            "SELECT {} \
+            FROM pg_settings \
+            WHERE left(name, length($1) + 1) = $1 || '.'",
+            Setting::tlist(pgver).join(", "),
+        );
+
+        let rows = session
+            .query(&sql[..], &[&extname])
+            .expect("Could not get pg_settings rows");
+
+        for row in &rows {
+            let setting = Setting::from_row(row);
+            settings.insert(setting.name.clone(), setting);
+        }
+
+        settings
+    }
+}
+
+// Custom GUCs of the extension, or None if they couldn't be retrieved, in
+// which case they're not compared.
+#[derive(Debug)]
+pub struct Settings {
+    settings: Option<BTreeMap<String, Setting>>,
+}
+
+impl Settings {
+    // GUCs stay defined once a library is loaded, so they're retrieved in a
+    // fresh session after loading only the libraries used by the current
+    // version, rather than in the session used to install and update the
+    // extension.  Without any library, the extension can't define any GUC.
+    //
+    // Non-superusers can only load libraries from $libdir/plugins, so the GUCs
+    // can't be retrieved if any library can't be loaded.
+    pub fn snapshot(
+        client: &mut Transaction,
+        new_session: &dyn Fn() -> Result<Client, postgres::Error>,
+        extname: &str,
+        pgver: u32,
+    ) -> Self {
+        let libraries = Setting::libraries(client, extname);
+
+        if libraries.is_empty() {
+            return Settings {
+                settings: Some(BTreeMap::new()),
+            };
+        }
+
+        let mut session = match new_session() {
+            Ok(s) => s,
+            Err(e) => {
+                elog(
+                    WARNING,
+                    &format!("Could not open a new session, GUCs not compared: {}", e),
+                );
+                return Settings { settings: None };
+            }
+        };
+
+        for lib in &libraries {
+            if let Err(e) = session.simple_query(&format!("LOAD '{}'", lib.replace('\'', "''"))) {
+                let msg = match e.as_db_error() {
+                    Some(db) => db.message().to_string(),
+                    None => e.to_string(),
+                };
+
+                elog(
+                    WARNING,
+                    &format!("Could not load library {}, GUCs not compared: {}", lib, msg),
+                );
+                return Settings { settings: None };
+            }
+        }
+
+        Settings {
+            settings: Some(Setting::snapshot(&mut session, extname, pgver)),
+        }
+    }
+}
+
+impl<'a> Compare<'a> for Settings {
+    fn compare(&'a self, other: &'a Self) -> Option<SchemaDiff<'a>> {
+        match (&self.settings, &other.settings) {
+            (Some(s), Some(o)) => s.compare(o),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Setting, Settings};
+    use crate::compare::*;
+    use std::collections::BTreeMap;
+
+    fn get_setting(name: &str, boot_val: &str) -> Setting {
+        Setting {
+            name: String::from(name),
+            vartype: String::from("bool"),
+            context: String::from("user"),
+            unit: None,
+            boot_val: Some(String::from(boot_val)),
+            min_val: None,
+            max_val: None,
+            enumvals: None,
+            short_desc: None,
+        }
+    }
+
+    fn get_settings(settings: Vec<Setting>) -> BTreeMap<String, Setting> {
+        settings.into_iter().map(|s| (s.name.clone(), s)).collect()
+    }
+
+    #[test]
+    fn compare_settings() {
+        let ins = get_settings(vec![
+            get_setting("myext.a", "on"),
+            get_setting("myext.b", "on"),
+        ]);
+        let upg = get_settings(vec![get_setting("myext.a", "off")]);
+
+        let msg = ins
+            .compare(&upg)
+            .expect("Should find differences")
+            .to_string();
+
+        assert!(
+            msg.contains("Setting missing in upgraded") && msg.contains("- myext.b"),
+            "Setting only defined in installed should be detected\n{}",
+            msg
+        );
+
+        assert!(
+            msg.contains("for Setting myext.a")
+                && msg.contains("in boot_val")
+                && msg.contains("- on")
+                && msg.contains("+ off"),
+            "Setting boot_val change should be detected\n{}",
+            msg
+        );
+
+        assert!(ins.compare(&ins).is_none());
+
+        // Settings that couldn't be retrieved on either side aren't compared
+        let ins = Settings {
+            settings: Some(ins),
+        };
+        let upg = Settings { settings: None };
+
+        assert!(ins.compare(&upg).is_none());
+        assert!(upg.compare(&ins).is_none());
+    }
+}
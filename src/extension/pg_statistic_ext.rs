@@ -15,12 +15,26 @@ DbStruct! {
             JOIN pg_attribute a ON (s.stxrelid = a.attrelid AND \
                 a.attnum = u.attnum AND NOT a.attisdropped))") {..PG_14},
         stxkind: Vec<Char>,
-        stxstattarget: Integer = ("s.stxstattarget::integer") {PG_13..},
+        // NULL means default since pg17, previously -1
+        stxstattarget: Integer = ("coalesce(s.stxstattarget::integer, -1)") {PG_13..},
         comment: Option<Text> = ("obj_description(s.oid, 'pg_statistic_ext')"),
     }
 }
 
 impl ExtendedStatistic {
+    fn sql(pgver: u32, qual: &str) -> String {
+        assert!(pgver >= PG_MIN_VER);
+
+        format!(
+            "SELECT {} \
+            FROM pg_statistic_ext s \
+            JOIN pg_roles r on r.oid = s.stxowner \
+            WHERE {} = $1",
+            ExtendedStatistic::tlist(pgver).join(", "),
+            qual,
+        )
+    }
+
     pub fn snapshot(
         client: &mut Transaction,
         relid: u32,
@@ -28,15 +42,7 @@ impl ExtendedStatistic {
     ) -> BTreeMap<String, ExtendedStatistic> {
         let mut statistics = BTreeMap::new();
 
-        assert!(pgver >= PG_MIN_VER);
-
-        let sql = format!(
-            "SELECT {} \
-            FROM pg_statistic_ext s \
-            JOIN pg_roles r on r.oid = s.stxowner \
-            WHERE stxrelid = $1",
-            ExtendedStatistic::tlist(pgver).join(", "),
-        );
+        let sql = ExtendedStatistic::sql(pgver, "stxrelid");
 
         let rows = client
             .query(&sql[..], &[&relid])
@@ -50,4 +56,28 @@ impl ExtendedStatistic {
 
         statistics
     }
+
+    // Extended statistics explicitly added to the extension, usually on a
+    // table that doesn't belong to the extension.
+    pub fn snapshot_members(
+        client: &mut Transaction,
+        oids: Vec<u32>,
+        pgver: u32,
+    ) -> BTreeMap<String, ExtendedStatistic> {
+        let mut statistics = BTreeMap::new();
+
+        let sql = ExtendedStatistic::sql(pgver, "s.oid");
+
+        for oid in oids {
+            let row = client
+                .query_one(&sql[..], &[&oid])
+                .expect("Could not get pg_statistic_ext row");
+
+            let stat = ExtendedStatistic::from_row(&row);
+
+            statistics.insert(stat.stxname.clone(), stat);
+        }
+
+        statistics
+    }
 }
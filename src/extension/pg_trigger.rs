@@ -8,6 +8,14 @@ DbStruct! {
         tgparentid: Name = ("tgparentid::regclass::text") {PG_13..},
         tgname: Name,
         tgdef: Text = ("pg_get_triggerdef(oid)"),
+        tgenabled: Char,
+        // Only relevant for constraint triggers
+        tgconstraint: Option<Name> = ("(SELECT c.conname \
+            FROM pg_constraint c \
+            WHERE c.oid = tgconstraint)"),
+        tgdeferrable: Bool,
+        tginitdeferred: Bool,
+        comment: Option<Text> = ("obj_description(oid, 'pg_trigger')"),
     }
 }
 
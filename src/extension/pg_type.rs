@@ -6,8 +6,9 @@ use postgres::{Row, Transaction};
 use std::collections::BTreeMap;
 
 use crate::{
-    compare::*, elog::*, extension::pg_class::Relation, extension::pg_range::Range,
-    pgdiff::SchemaDiff, pgtype::*, proc_prototype, CompareStruct, DbStruct,
+    compare::*, elog::*, extension::pg_class::Relation, extension::pg_constraint::Constraint,
+    extension::pg_range::Range, extension::SnapshotOptions, init_privs, pgdiff::SchemaDiff,
+    pgtype::*, proc_prototype, seclabels, CompareStruct, DbStruct,
 };
 
 DbStruct! {
@@ -35,12 +36,18 @@ DbStruct! {
         typalign: Char,
         typstorage: Char,
         typnotnull: Bool,
+        typbasetype: Option<Text> = ("CASE WHEN t.typbasetype = 0 THEN NULL \
+            ELSE format_type(t.typbasetype, t.typtypmod) END"),
+        typtypmod: Integer,
         typndims: Integer,
         typcollation: Option<Name> = ("c.collname"),
         typdefault: Option<Text>,
-        typacl: Option<Text> = ("t.typacl::text"),
+        typacl: Option<Text> = ("t.typacl::text") {PG_9_2..},
+        initprivs: Option<Text> = (init_privs!("pg_type", "t.oid")) {PG_9_6..},
         typenum: Option<Vec<Text>> = ("(SELECT array_agg(e.enumlabel || '=' || \
             e.enumsortorder) FROM pg_enum e WHERE enumtypid = t.oid)"),
+        comment: Option<Text> = ("obj_description(t.oid, 'pg_type')"),
+        seclabels: Option<SecLabels> = (seclabels!("pg_type", "t.oid")),
     }
 }
 
@@ -49,6 +56,7 @@ CompareStruct! {
         typ: PgType,
         relation: Option<Relation>,
         range: Option<Range>,
+        constraints: BTreeMap<String, Constraint>,
     }
 }
 
@@ -57,11 +65,12 @@ impl Type {
         client: &mut Transaction,
         oids: Vec<u32>,
         pgver: u32,
+        options: SnapshotOptions,
     ) -> BTreeMap<String, Type> {
         let mut types = BTreeMap::new();
 
         for oid in oids {
-            let typ = snap_one_type(client, oid, pgver);
+            let typ = snap_one_type(client, oid, pgver, options);
             types.insert(typ.typ.typname.clone(), typ);
         }
 
@@ -69,7 +78,12 @@ impl Type {
     }
 }
 
-pub fn snap_one_type(client: &mut Transaction, oid: u32, pgver: u32) -> Type {
+pub fn snap_one_type(
+    client: &mut Transaction,
+    oid: u32,
+    pgver: u32,
+    options: SnapshotOptions,
+) -> Type {
     let sql = format!(
         "SELECT {}, typrelid as __typrelid \
         FROM pg_type t \
@@ -101,7 +115,7 @@ pub fn snap_one_type(client: &mut Transaction, oid: u32, pgver: u32) -> Type {
 
     let mut relation = None;
     if typrelid != 0 {
-        let mut relations = Relation::snapshot(client, vec![typrelid], pgver);
+        let mut relations = Relation::snapshot(client, vec![typrelid], pgver, options);
         match relations.remove_entry(&typ.typname) {
             None => {
                 elog(
@@ -120,6 +134,10 @@ pub fn snap_one_type(client: &mut Transaction, oid: u32, pgver: u32) -> Type {
         ident: typ.typname.clone(),
         typ,
         relation,
-        range: Range::snapshot(client, oid, pgver),
+        range: match pgver {
+            PG_9_2..=PG_MAX => Range::snapshot(client, oid, pgver),
+            _ => None,
+        },
+        constraints: Constraint::snapshot_per_type(client, oid, pgver),
     }
 }
@@ -0,0 +1,49 @@
+/*----------------------------------------------------------------------------
+ * Author: Julien Rouhaud
+ * Copyright: Copyright (c) 2021-2026 : Julien Rouhaud - All rights reserved
+ *---------------------------------------------------------------------------*/
+use postgres::{Row, Transaction};
+use std::collections::BTreeMap;
+
+use crate::{compare::*, pgdiff::SchemaDiff, pgtype::*, DbStruct};
+
+DbStruct! {
+    UserMapping:umuser:UserMapping {
+        umuser: Name = ("um.usename"),
+        umserver: Name = ("um.srvname"),
+        umoptions: Option<ClassOptions> = ("um.umoptions"),
+    }
+}
+
+impl UserMapping {
+    // User mappings are not recorded as extension members, so they're
+    // retrieved for each foreign server owned by the extension.
+    pub fn snapshot(
+        client: &mut Transaction,
+        srvid: u32,
+        pgver: u32,
+    ) -> BTreeMap<String, UserMapping> {
+        let mut ums = BTreeMap::new();
+
+        // Use pg_user_mappings rather than pg_user_mapping, as the latter is
+        // only readable by superusers.  PUBLIC mappings are reported as
+        // "public".
+        let sql = format!(
+            "SELECT {} \
+            FROM pg_user_mappings um \
+            WHERE um.srvid = $1",
+            UserMapping::tlist(pgver).join(", "),
+        );
+
+        let rows = client
+            .query(&sql[..], &[&srvid])
+            .expect("Could not get pg_user_mappings rows");
+
+        for row in &rows {
+            let um = UserMapping::from_row(row);
+            ums.insert(um.umuser.clone(), um);
+        }
+
+        ums
+    }
+}
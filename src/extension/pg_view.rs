@@ -0,0 +1,40 @@
+/*----------------------------------------------------------------------------
+ * Author: Julien Rouhaud
+ * Copyright: Copyright (c) 2021-2026 : Julien Rouhaud - All rights reserved
+ *---------------------------------------------------------------------------*/
+use postgres::{Row, Transaction};
+
+use crate::{compare::*, pgdiff::SchemaDiff, pgtype::*, DbStruct};
+
+DbStruct! {
+    View:viewname:View {
+        viewname: Text = ("c.oid::regclass::text"),
+        viewdef: Text = ("pg_get_viewdef(c.oid, true)"),
+        check_option: Option<Text> = ("(SELECT option_value \
+            FROM pg_options_to_table(c.reloptions) \
+            WHERE option_name = 'check_option')") {PG_9_4..},
+        security_barrier: Option<Text> = ("(SELECT option_value \
+            FROM pg_options_to_table(c.reloptions) \
+            WHERE option_name = 'security_barrier')"),
+        security_invoker: Option<Text> = ("(SELECT option_value \
+            FROM pg_options_to_table(c.reloptions) \
+            WHERE option_name = 'security_invoker')") {PG_15..},
+    }
+}
+
+impl View {
+    pub fn snapshot(client: &mut Transaction, relid: u32, pgver: u32) -> View {
+        let sql = format!(
+            "SELECT {} \
+            FROM pg_class c \
+            WHERE c.oid = $1",
+            View::tlist(pgver).join(", "),
+        );
+
+        let row = client
+            .query_one(&sql[..], &[&relid])
+            .expect("Could not get view definition");
+
+        View::from_row(&row)
+    }
+}
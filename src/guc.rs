@@ -14,7 +14,7 @@ impl Guc {
             FROM pg_settings";
 
         let rows = client
-            .query(&sql[..], &[])
+            .query(sql, &[])
             .expect("Could not get pg_settings rows");
 
         for row in rows {
@@ -11,12 +11,12 @@ use serde::Deserialize;
 use toml::Value;
 
 mod extension;
-use crate::extension::Extension;
+use crate::extension::{Extension, SnapshotOptions};
 mod guc;
 
 #[macro_use]
 mod compare;
-use crate::compare::{Compare, PG_9_6};
+use crate::compare::{Compare, PG_9_1, PG_9_3, PG_9_6};
 
 mod pgdiff;
 mod pgtype;
@@ -55,6 +55,12 @@ struct Config {
     schema: Option<String>,
     extra_queries: Option<Vec<String>>,
     pre_upgrade_queries: Option<Vec<String>>,
+    sequence_values: Option<bool>,
+    strict: Option<bool>,
+    physical_layout: Option<bool>,
+    all_paths: Option<bool>,
+    alternative_paths: Option<bool>,
+    roundtrip: Option<bool>,
 }
 
 impl<'a> Config {
@@ -70,6 +76,12 @@ impl<'a> Config {
             schema: None,
             extra_queries: None,
             pre_upgrade_queries: None,
+            sequence_values: None,
+            strict: None,
+            physical_layout: None,
+            all_paths: None,
+            alternative_paths: None,
+            roundtrip: None,
         }
     }
 
@@ -87,30 +99,15 @@ impl<'a> Config {
             };
         }
 
-        if self.from.is_none() || matches.occurrences_of("from") != 0 {
-            self.from = match matches.value_of("from") {
-                Some(e) => Some(String::from(e)),
-                None => {
-                    clap::Error::with_description(
-                        "--from is required",
-                        ErrorKind::MissingRequiredArgument,
-                    )
-                    .exit();
-                }
-            };
+        // --from is only required if --all-paths isn't used, which is checked
+        // once all the options are known.  If not provided, --to defaults to
+        // the extension's default_version.
+        if let Some(from) = matches.value_of("from") {
+            self.from = Some(String::from(from));
         }
 
-        if self.to.is_none() || matches.occurrences_of("to") != 0 {
-            self.to = match matches.value_of("to") {
-                Some(e) => Some(String::from(e)),
-                None => {
-                    clap::Error::with_description(
-                        "--to is required",
-                        ErrorKind::MissingRequiredArgument,
-                    )
-                    .exit();
-                }
-            };
+        if let Some(to) = matches.value_of("to") {
+            self.to = Some(String::from(to));
         }
 
         if self.host.is_none() || matches.occurrences_of("host") != 0 {
@@ -142,7 +139,31 @@ impl<'a> Config {
         }
 
         if self.schema.is_none() || matches.occurrences_of("schema") != 0 {
-            self.schema = matches.value_of("schema").map(|s| String::from(s));
+            self.schema = matches.value_of("schema").map(String::from);
+        }
+
+        if matches.is_present("sequence_values") {
+            self.sequence_values = Some(true);
+        }
+
+        if matches.is_present("strict") {
+            self.strict = Some(true);
+        }
+
+        if matches.is_present("physical_layout") {
+            self.physical_layout = Some(true);
+        }
+
+        if matches.is_present("all_paths") {
+            self.all_paths = Some(true);
+        }
+
+        if matches.is_present("alternative_paths") {
+            self.alternative_paths = Some(true);
+        }
+
+        if matches.is_present("roundtrip") {
+            self.roundtrip = Some(true);
         }
     }
 
@@ -161,7 +182,13 @@ impl<'a> Config {
                 | "dbname"
                 | "schema"
                 | "extra_queries"
-                | "pre_upgrade_queries" => {}
+                | "pre_upgrade_queries"
+                | "sequence_values"
+                | "strict"
+                | "physical_layout"
+                | "all_paths"
+                | "alternative_paths"
+                | "roundtrip" => {}
                 _ => {
                     elog(WARNING, &format!("Unexpected {} key \"{}\"", format, k));
                 }
@@ -241,8 +268,8 @@ impl<'a> Config {
 
 pub struct App {
     extname: String,
-    from: String,
-    to: String,
+    from: Option<String>,
+    to: Option<String>,
     host: String,
     port: u16,
     user: String,
@@ -250,6 +277,18 @@ pub struct App {
     schema: Option<String>,
     extra_queries: Vec<String>,
     pre_upgrade_queries: Vec<String>,
+    sequence_values: bool,
+    strict: bool,
+    physical_layout: bool,
+    all_paths: bool,
+    alternative_paths: bool,
+    roundtrip: bool,
+}
+
+impl Default for App {
+    fn default() -> Self {
+        App::new()
+    }
 }
 
 impl App {
@@ -285,17 +324,15 @@ impl App {
                     .takes_value(true)
                     .help("extension to test"),
             )
-            .arg(
-                Arg::with_name("from")
-                    .long("from")
-                    .takes_value(true)
-                    .help("initial version of the extension"),
-            )
+            .arg(Arg::with_name("from").long("from").takes_value(true).help(
+                "initial version(s) of the extension, as a comma \
+                separated list of versions or patterns, or \"all\"",
+            ))
             .arg(
                 Arg::with_name("to")
                     .long("to")
                     .takes_value(true)
-                    .help("upgraded version of the extension"),
+                    .help("upgraded version of the extension, defaults to its default_version"),
             )
             .arg(
                 Arg::with_name("host")
@@ -331,6 +368,36 @@ impl App {
                     .long("schema")
                     .help("schema to install the extension in"),
             )
+            .arg(
+                Arg::with_name("sequence_values")
+                    .long("sequence-values")
+                    .help("also compare the sequences last_value and is_called"),
+            )
+            .arg(
+                Arg::with_name("strict")
+                    .long("strict")
+                    .help("error out if an extension member isn't handled"),
+            )
+            .arg(
+                Arg::with_name("physical_layout")
+                    .long("physical-layout")
+                    .help("also compare the physical layout of the tables and composite types"),
+            )
+            .arg(
+                Arg::with_name("all_paths")
+                    .long("all-paths")
+                    .help("validate all the available update paths of the extension"),
+            )
+            .arg(
+                Arg::with_name("alternative_paths")
+                    .long("alternative-paths")
+                    .help("also validate all the alternative update paths between two versions"),
+            )
+            .arg(
+                Arg::with_name("roundtrip")
+                    .long("roundtrip")
+                    .help("also validate the downgrade back to the initial version"),
+            )
             .arg(
                 Arg::with_name("filename")
                     .short("c")
@@ -361,7 +428,14 @@ impl App {
 
         config.apply_matches(&matches);
 
-        if config.from == config.to {
+        let all_paths = config.all_paths.unwrap_or(false);
+
+        if !all_paths && config.from.is_none() {
+            clap::Error::with_description("--from is required", ErrorKind::MissingRequiredArgument)
+                .exit();
+        }
+
+        if !all_paths && config.from == config.to {
             clap::Error::with_description(
                 "--from and --to must be different",
                 ErrorKind::InvalidValue,
@@ -371,8 +445,8 @@ impl App {
 
         App {
             extname: config.extname.unwrap(),
-            from: config.from.unwrap(),
-            to: config.to.unwrap(),
+            from: config.from,
+            to: config.to,
             host: config.host.unwrap(),
             port: config.port.unwrap(),
             user: config.user.unwrap(),
@@ -380,6 +454,20 @@ impl App {
             schema: config.schema,
             extra_queries: config.extra_queries.unwrap(),
             pre_upgrade_queries: config.pre_upgrade_queries.unwrap(),
+            sequence_values: config.sequence_values.unwrap_or(false),
+            strict: config.strict.unwrap_or(false),
+            physical_layout: config.physical_layout.unwrap_or(false),
+            all_paths,
+            alternative_paths: config.alternative_paths.unwrap_or(false),
+            roundtrip: config.roundtrip.unwrap_or(false),
+        }
+    }
+
+    fn snapshot_options(&self) -> SnapshotOptions {
+        SnapshotOptions {
+            sequence_values: self.sequence_values,
+            strict: self.strict,
+            physical_layout: self.physical_layout,
         }
     }
 
@@ -391,8 +479,7 @@ impl App {
     }
 
     fn connect(&self) -> Result<(Client, u32), postgres::Error> {
-        let conninfo = self.conninfo();
-        let mut client = Client::connect(&conninfo, NoTls)?;
+        let mut client = self.new_session()?;
 
         let rows = client.query("SHOW server_version_num", &[])?;
         let ver: &str = rows[0].get(0);
@@ -401,12 +488,16 @@ impl App {
         Ok((client, ver.parse().unwrap()))
     }
 
-    fn error(msg: String) {
+    fn new_session(&self) -> Result<Client, postgres::Error> {
+        Client::connect(&self.conninfo(), NoTls)
+    }
+
+    fn error(msg: String) -> ! {
         println!("ERROR: {}", msg);
         process::exit(1);
     }
 
-    fn check_ext(&self, client: &mut postgres::Client) {
+    fn check_ext(&self, client: &mut postgres::Client, from: &str, to: &str) -> Result<(), String> {
         let rows = client
             .query(
                 "SELECT version \
@@ -417,7 +508,7 @@ impl App {
             .expect("Could not query pg_available_extension_versions");
 
         if rows.is_empty() {
-            App::error(format!("extension \"{}\" does not exits", self.extname));
+            return Err(format!("extension \"{}\" does not exist", self.extname));
         }
 
         let mut found_from = false;
@@ -427,9 +518,9 @@ impl App {
         for row in &rows {
             let ver: &str = row.get(0);
 
-            if ver == self.from {
+            if ver == from {
                 found_from = true;
-            } else if ver == self.to {
+            } else if ver == to {
                 found_to = true;
             } else {
                 alt.push(ver);
@@ -437,18 +528,20 @@ impl App {
         }
 
         if !found_from {
-            App::error(format!(
+            return Err(format!(
                 "version \"{}\" of extension \"{}\" not found",
-                self.from, self.extname
+                from, self.extname
             ));
         }
 
         if !found_to {
-            App::error(format!(
+            return Err(format!(
                 "version \"{}\" of extension \"{}\" not found",
-                self.to, self.extname
+                to, self.extname
             ));
         }
+
+        Ok(())
     }
 
     fn install_version(
@@ -456,7 +549,7 @@ impl App {
         client: &mut postgres::Transaction,
         pgver: u32,
         extver: &str,
-    ) -> (Guc, Guc) {
+    ) -> Result<(Guc, Guc), String> {
         let cascade = match pgver >= PG_9_6 {
             true => "CASCADE",
             false => "",
@@ -465,8 +558,16 @@ impl App {
         let schema = match self.schema {
             Some(ref s) => {
                 // make sure that the target schema exists
-                if let Err(e) = client.simple_query(&format!("CREATE SCHEMA IF NOT EXISTS {}", s)) {
-                    App::error(e.to_string());
+                let sql = match pgver >= PG_9_3 {
+                    true => format!("CREATE SCHEMA IF NOT EXISTS {}", s),
+                    false => format!(
+                        "DO $$BEGIN CREATE SCHEMA {}; \
+                        EXCEPTION WHEN duplicate_schema THEN NULL; END$$",
+                        s
+                    ),
+                };
+                if let Err(e) = client.simple_query(&sql) {
+                    return Err(e.to_string());
                 }
                 format!("SCHEMA {}", s)
             }
@@ -478,7 +579,7 @@ impl App {
             "CREATE EXTENSION {} VERSION '{}' {} {} ;",
             self.extname, extver, schema, cascade
         )) {
-            App::error(e.to_string());
+            return Err(e.to_string());
         };
 
         let guc_post = Guc::snapshot(client, String::from(extver));
@@ -487,21 +588,24 @@ impl App {
             .execute("RESET ALL", &[])
             .expect("Could not execute RESET ALL");
 
-        (guc_pre, guc_post)
+        Ok((guc_pre, guc_post))
     }
 
-    fn update_version(&self, client: &mut postgres::Transaction) -> (Guc, Guc) {
-        let guc_ver = String::from(format!("{}--{}", self.from, self.to,));
+    fn update_version(
+        &self,
+        client: &mut postgres::Transaction,
+        from: &str,
+        to: &str,
+    ) -> Result<(Guc, Guc), String> {
+        let guc_ver = format!("{}--{}", from, to);
 
         let guc_pre = Guc::snapshot(client, guc_ver.clone());
 
-        self.run_queries(client, &self.pre_upgrade_queries, false);
-
         if let Err(e) = client.simple_query(&format!(
             "ALTER EXTENSION {} UPDATE TO '{}'",
-            self.extname, self.to
+            self.extname, to
         )) {
-            App::error(e.to_string());
+            return Err(e.to_string());
         };
 
         let guc_post = Guc::snapshot(client, guc_ver);
@@ -510,7 +614,7 @@ impl App {
             .execute("RESET ALL", &[])
             .expect("Could not execute RESET ALL");
 
-        (guc_pre, guc_post)
+        Ok((guc_pre, guc_post))
     }
 
     fn run_queries(
@@ -557,59 +661,450 @@ impl App {
         ExecutedQueries::new_from(result)
     }
 
-    pub fn run(&self) -> Result<(), String> {
-        let (mut client, pgver) = match self.connect() {
-            Ok(c) => c,
-            Err(e) => {
-                return Err(e.to_string());
+    // Returns all the (source, target) pairs of versions for which an update
+    // path exists.  Only versions having an install script are returned, as
+    // other versions can only be reached through an update.
+    fn update_paths(&self, client: &mut postgres::Client) -> Vec<(String, String)> {
+        let rows = client
+            .query(
+                "SELECT p.source, p.target \
+            FROM pg_extension_update_paths($1) p \
+            JOIN pg_available_extension_versions s \
+                ON s.name = $1 AND s.version = p.source \
+            JOIN pg_available_extension_versions t \
+                ON t.name = $1 AND t.version = p.target \
+            WHERE p.path IS NOT NULL \
+            ORDER BY p.source, p.target",
+                &[&self.extname],
+            )
+            .expect("Could not query pg_extension_update_paths");
+
+        rows.iter().map(|r| (r.get(0), r.get(1))).collect()
+    }
+
+    fn snapshot(&self, client: &mut postgres::Transaction, pgver: u32) -> Extension {
+        // Custom GUCs can't be undefined, so a new session is used for each
+        // snapshot to only see the ones of the current version.
+        let mut ext = Extension::snapshot(
+            &self.extname,
+            client,
+            &|| self.new_session(),
+            pgver,
+            self.snapshot_options(),
+        );
+        ext.set_extra_queries(self.run_queries(client, &self.extra_queries, true));
+
+        ext
+    }
+
+    // Install the first version of the given path and update it through all
+    // the other versions of the path.
+    fn install_and_update(
+        &self,
+        client: &mut postgres::Transaction,
+        pgver: u32,
+        path: &[String],
+        result: &mut String,
+    ) -> Result<Extension, String> {
+        let (pre, post) = self.install_version(client, pgver, &path[0])?;
+        if let Some(d) = pre.compare(&post) {
+            result.push_str(&d.to_string());
+        }
+
+        self.run_queries(client, &self.pre_upgrade_queries, false);
+
+        for step in path.windows(2) {
+            let (pre, post) = self.update_version(client, &step[0], &step[1])?;
+            if let Some(d) = pre.compare(&post) {
+                result.push_str(&d.to_string());
             }
-        };
+        }
+
+        let upgraded = self.snapshot(client, pgver);
+
+        let to = path.last().unwrap();
+        if upgraded.extversion() != to {
+            result.push_str(&format!(
+                "Extension version after upgrade is \"{}\" instead of \"{}\"\n\n",
+                upgraded.extversion(),
+                to
+            ));
+        }
+
+        Ok(upgraded)
+    }
+
+    // Returns all the distinct update paths going from a version to another
+    // using the available update scripts, except the one PostgreSQL would
+    // choose.  Each path is the list of all the versions to go through,
+    // including the source and target versions.
+    fn alternative_paths(
+        &self,
+        client: &mut postgres::Transaction,
+        from: &str,
+        to: &str,
+    ) -> Vec<Vec<String>> {
+        let rows = client
+            .query(
+                "SELECT source, target, path \
+            FROM pg_extension_update_paths($1) \
+            WHERE path IS NOT NULL \
+            ORDER BY source, target",
+                &[&self.extname],
+            )
+            .expect("Could not query pg_extension_update_paths");
+
+        // Direct edges of the update graph, ie. the available update scripts
+        let mut edges: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        let mut default_path: Vec<String> = vec![];
+
+        for row in &rows {
+            let source: String = row.get(0);
+            let target: String = row.get(1);
+            let path: String = row.get(2);
 
-        self.check_ext(&mut client);
+            if source == from && target == to {
+                default_path = path.split("--").map(String::from).collect();
+            }
+
+            if path == format!("{}--{}", source, target) {
+                edges.entry(source).or_default().push(target);
+            }
+        }
+
+        let (paths, truncated) =
+            update_graph_paths(&edges, from, to, &default_path, MAX_ALTERNATIVE_PATHS);
+
+        if truncated {
+            elog(
+                WARNING,
+                &format!(
+                    "Too many update paths from {} to {}, only the first {} are validated",
+                    from, to, MAX_ALTERNATIVE_PATHS
+                ),
+            );
+        }
+
+        paths
+    }
+
+    // Downgrade the extension, currently in the target version, back to the
+    // source version and compare it with a fresh install of the source
+    // version.
+    fn validate_roundtrip(
+        &self,
+        client: &mut postgres::Transaction,
+        pgver: u32,
+        from: &str,
+        to: &str,
+        result: &mut String,
+    ) -> Result<(), String> {
+        let has_path: bool = client
+            .query_one(
+                "SELECT count(*) > 0 \
+            FROM pg_extension_update_paths($1) \
+            WHERE source = $2 AND target = $3 \
+            AND path IS NOT NULL",
+                &[&self.extname, &to, &from],
+            )
+            .expect("Could not query pg_extension_update_paths")
+            .get(0);
 
+        if !has_path {
+            result.push_str(&format!("No downgrade path from {} to {}\n\n", to, from));
+            return Ok(());
+        }
+
+        let (pre, post) = self.update_version(client, to, from)?;
+        if let Some(d) = pre.compare(&post) {
+            result.push_str(&d.to_string());
+        }
+
+        let downgraded = self.snapshot(client, pgver);
+
+        if downgraded.extversion() != from {
+            result.push_str(&format!(
+                "Extension version after downgrade is \"{}\" instead of \"{}\"\n\n",
+                downgraded.extversion(),
+                from
+            ));
+        }
+
+        client
+            .simple_query(&format!("DROP EXTENSION {} CASCADE", self.extname))
+            .expect("Could not execute DROP EXTENSION");
+
+        // GUC changes for this version were already checked when installing
+        // it before the upgrade.
+        self.install_version(client, pgver, from)?;
+        self.run_queries(client, &self.pre_upgrade_queries, false);
+
+        let reinstalled = self.snapshot(client, pgver);
+
+        if let Some(m) = reinstalled.compare(&downgraded) {
+            result.push_str(&format!(
+                "Round trip {} -> {} -> {}:\n{}",
+                from, to, from, m
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn validate(
+        &self,
+        client: &mut postgres::Client,
+        pgver: u32,
+        from: &str,
+        to: &str,
+    ) -> Result<Option<String>, String> {
         let mut transaction = client.transaction().expect("Could not start a transaction");
 
+        let result = self.validate_pair(&mut transaction, pgver, from, to);
+
+        transaction
+            .rollback()
+            .expect("Could not rollback the transaction");
+
+        result.map(|r| match r.len() {
+            0 => None,
+            _ => Some(r),
+        })
+    }
+
+    // Do all the work for validating the update from a version to another in
+    // the given transaction, returning the differences found if any.  An error
+    // is returned if any of the scripts couldn't be executed.
+    fn validate_pair(
+        &self,
+        transaction: &mut postgres::Transaction,
+        pgver: u32,
+        from: &str,
+        to: &str,
+    ) -> Result<String, String> {
         let mut result = String::new();
 
         // First round installing directly the target version
-        let (pre, post) = self.install_version(&mut transaction, pgver, &self.to);
+        let (pre, post) = self.install_version(transaction, pgver, to)?;
 
         if let Some(d) = pre.compare(&post) {
             result.push_str(&d.to_string());
         }
 
-        let mut from = Extension::snapshot(&self.extname, &mut transaction, pgver);
-        from.set_extra_queries(self.run_queries(&mut transaction, &self.extra_queries, true));
+        let installed = self.snapshot(transaction, pgver);
 
-        // Remove the extension
+        // Remove the extension.  Use CASCADE as some objects created by the
+        // extension script, like user mappings, aren't extension members.
+        let drop = format!("DROP EXTENSION {} CASCADE", self.extname);
         transaction
-            .simple_query(&format!("DROP EXTENSION {}", self.extname))
+            .simple_query(&drop)
             .expect("Could not execute DROP EXTENSION");
 
         // Second round, install source version and update it
-        let (pre, post) = self.install_version(&mut transaction, pgver, &self.from);
-        if let Some(d) = pre.compare(&post) {
-            result.push_str(&d.to_string());
+        let path = [String::from(from), String::from(to)];
+        let upgraded = self.install_and_update(transaction, pgver, &path, &mut result)?;
+
+        if let Some(m) = installed.compare(&upgraded) {
+            result.push_str(&m.to_string());
+        };
+
+        if self.roundtrip {
+            self.validate_roundtrip(transaction, pgver, from, to, &mut result)?;
         }
 
-        let (pre, post) = self.update_version(&mut transaction);
-        if let Some(d) = pre.compare(&post) {
-            result.push_str(&d.to_string());
+        // Optionally force all the other update paths, and compare them with
+        // both the fresh install and the update through the default path.
+        if self.alternative_paths {
+            for path in self.alternative_paths(transaction, from, to) {
+                transaction
+                    .simple_query(&drop)
+                    .expect("Could not execute DROP EXTENSION");
+
+                let alternative =
+                    self.install_and_update(transaction, pgver, &path, &mut result)?;
+                let path = path.join("--");
+
+                if let Some(m) = installed.compare(&alternative) {
+                    result.push_str(&format!("Update through path {}:\n{}", path, m));
+                }
+
+                if let Some(m) = upgraded.compare(&alternative) {
+                    result.push_str(&format!(
+                        "Differences between the default update path \
+                        (installed) and update path {} (upgraded):\n{}",
+                        path, m
+                    ));
+                }
+            }
         }
 
-        let mut to = Extension::snapshot(&self.extname, &mut transaction, pgver);
-        to.set_extra_queries(self.run_queries(&mut transaction, &self.extra_queries, true));
+        Ok(result)
+    }
 
-        let res = from.compare(&to);
+    // Returns the default version of the extension, as specified in its
+    // control file.
+    fn default_version(&self, client: &mut postgres::Client) -> String {
+        let rows = client
+            .query(
+                "SELECT default_version \
+            FROM pg_available_extensions \
+            WHERE name = $1",
+                &[&self.extname],
+            )
+            .expect("Could not query pg_available_extensions");
 
-        transaction
-            .rollback()
-            .expect("Could not rollback the transaction");
+        if rows.is_empty() {
+            App::error(format!("extension \"{}\" does not exist", self.extname));
+        }
 
-        if let Some(m) = res {
-            result.push_str(&m.to_string());
+        rows[0].get(0)
+    }
+
+    // Returns the source versions to validate, as specified by --from: a comma
+    // separated list of versions or patterns using "*" and "?" wildcards, "all"
+    // meaning any version that can be updated to the target version.  Only
+    // versions having an install script are considered when expanding the
+    // patterns.
+    fn source_versions(&self, client: &mut postgres::Client, from: &str, to: &str) -> Vec<String> {
+        let rows = client
+            .query(
+                "SELECT p.source \
+            FROM pg_extension_update_paths($1) p \
+            JOIN pg_available_extension_versions v \
+                ON v.name = $1 AND v.version = p.source \
+            WHERE p.target = $2 AND p.path IS NOT NULL \
+            ORDER BY p.source",
+                &[&self.extname, &to],
+            )
+            .expect("Could not query pg_extension_update_paths");
+        let candidates: Vec<String> = rows.iter().map(|r| r.get(0)).collect();
+
+        let mut sources: Vec<String> = Vec::new();
+
+        for pattern in from.split(',').map(str::trim) {
+            let pattern = match pattern {
+                "all" => "*",
+                p => p,
+            };
+
+            if !pattern.contains(&['*', '?'][..]) {
+                if pattern == to {
+                    App::error(String::from("--from and --to must be different"));
+                }
+
+                if !sources.iter().any(|s| s == pattern) {
+                    sources.push(String::from(pattern));
+                }
+                continue;
+            }
+
+            let matches: Vec<&String> = candidates
+                .iter()
+                .filter(|c| glob_match(pattern, c))
+                .collect();
+
+            if matches.is_empty() {
+                App::error(format!(
+                    "no version of extension \"{}\" matching \"{}\" can be updated to \"{}\"",
+                    self.extname, pattern, to
+                ));
+            }
+
+            for m in matches {
+                if !sources.contains(m) {
+                    sources.push(m.clone());
+                }
+            }
+        }
+
+        sources
+    }
+
+    pub fn run(&self) -> Result<(), String> {
+        let (mut client, pgver) = match self.connect() {
+            Ok(c) => c,
+            Err(e) => {
+                return Err(e.to_string());
+            }
         };
 
+        if pgver < PG_9_1 {
+            return Err(String::from(
+                "Extensions are only supported since PostgreSQL 9.1",
+            ));
+        }
+
+        // This also errors out if the extension isn't available, before
+        // looking for its update paths.
+        let default_version = self.default_version(&mut client);
+
+        let pairs = match self.all_paths {
+            true => self.update_paths(&mut client),
+            false => {
+                let to = match self.to {
+                    Some(ref t) => t.clone(),
+                    None => default_version,
+                };
+
+                self.source_versions(&mut client, self.from.as_ref().unwrap(), &to)
+                    .into_iter()
+                    .map(|from| (from, to.clone()))
+                    .collect()
+            }
+        };
+
+        if pairs.is_empty() {
+            return Err(format!(
+                "No update path found for extension \"{}\"",
+                self.extname
+            ));
+        }
+
+        // Simple case, report the differences as-is
+        if pairs.len() == 1 && !self.all_paths {
+            let (from, to) = &pairs[0];
+
+            if let Err(e) = self.check_ext(&mut client, from, to) {
+                App::error(e);
+            }
+
+            return match self.validate(&mut client, pgver, from, to) {
+                Ok(None) => Ok(()),
+                Ok(Some(r)) => Err(r),
+                Err(e) => App::error(e),
+            };
+        }
+
+        let mut summary = String::new();
+        let mut result = String::new();
+
+        // Report a pair that can't be installed or updated as failed, and keep
+        // going with the other pairs.
+        for (from, to) in &pairs {
+            let res = self
+                .check_ext(&mut client, from, to)
+                .and_then(|_| self.validate(&mut client, pgver, from, to));
+
+            match res {
+                Ok(None) => {
+                    summary.push_str(&format!("  {} -> {}: ok\n", from, to));
+                }
+                Err(e) => {
+                    summary.push_str(&format!("  {} -> {}: FAILED\n", from, to));
+                    result.push_str(&format!(
+                        "Update from {} to {}:\nERROR: {}\n\n",
+                        from, to, e
+                    ));
+                }
+                Ok(Some(r)) => {
+                    summary.push_str(&format!("  {} -> {}: FAILED\n", from, to));
+                    result.push_str(&format!("Update from {} to {}:\n{}", from, to, r));
+                }
+            }
+        }
+
+        println!("Validated {} update paths:\n{}", pairs.len(), summary);
+
         match result.len() {
             0 => Ok(()),
             _ => Err(result),
@@ -617,6 +1112,119 @@ impl App {
     }
 }
 
+// Maximum number of alternative update paths validated between two versions,
+// as the number of paths can grow exponentially with the number of update
+// scripts.
+const MAX_ALTERNATIVE_PATHS: usize = 50;
+
+// Returns the distinct paths going from a version to another in the given
+// update graph, except the default one, each path being the list of all the
+// versions to go through.  A path never goes through the same version twice,
+// and only versions that can lead to the target version are explored.  The
+// search stops after finding max paths, in which case true is also returned.
+fn update_graph_paths(
+    edges: &BTreeMap<String, Vec<String>>,
+    from: &str,
+    to: &str,
+    default_path: &[String],
+    max: usize,
+) -> (Vec<Vec<String>>, bool) {
+    fn walk(
+        edges: &BTreeMap<String, Vec<String>>,
+        useful: &[&String],
+        to: &str,
+        default_path: &[String],
+        max: usize,
+        path: &mut Vec<String>,
+        paths: &mut Vec<Vec<String>>,
+    ) -> bool {
+        let cur = path.last().unwrap().clone();
+
+        if cur == to {
+            if path != default_path {
+                paths.push(path.clone());
+            }
+
+            return paths.len() >= max;
+        }
+
+        for next in edges.get(&cur).into_iter().flatten() {
+            if path.contains(next) || !useful.contains(&next) {
+                continue;
+            }
+
+            path.push(next.clone());
+            let done = walk(edges, useful, to, default_path, max, path, paths);
+            path.pop();
+
+            if done {
+                return true;
+            }
+        }
+
+        false
+    }
+
+    // Versions from which the target version can be reached
+    let to = String::from(to);
+    let mut useful: Vec<&String> = vec![&to];
+    loop {
+        let prev = useful.len();
+
+        for (source, targets) in edges {
+            if !useful.contains(&source) && targets.iter().any(|t| useful.contains(&t)) {
+                useful.push(source);
+            }
+        }
+
+        if useful.len() == prev {
+            break;
+        }
+    }
+
+    let mut paths = vec![];
+    let truncated = walk(
+        edges,
+        &useful,
+        &to,
+        default_path,
+        max,
+        &mut vec![String::from(from)],
+        &mut paths,
+    );
+
+    (paths, truncated)
+}
+
+// Simple glob matching, only supporting the "*" and "?" wildcards.
+fn glob_match(pattern: &str, s: &str) -> bool {
+    let p: Vec<char> = pattern.chars().collect();
+    let s: Vec<char> = s.chars().collect();
+
+    // Index in the pattern of the last "*" found, and position in the string
+    // when it was found, to backtrack in case of mismatch.
+    let mut star: Option<(usize, usize)> = None;
+    let (mut pi, mut si) = (0, 0);
+
+    while si < s.len() {
+        if pi < p.len() && (p[pi] == '?' || p[pi] == s[si]) {
+            pi += 1;
+            si += 1;
+        } else if pi < p.len() && p[pi] == '*' {
+            star = Some((pi, si));
+            pi += 1;
+        } else if let Some((spi, ssi)) = star {
+            pi = spi + 1;
+            si = ssi + 1;
+            star = Some((spi, ssi + 1));
+        } else {
+            return false;
+        }
+    }
+
+    p[pi..].iter().all(|c| *c == '*')
+}
+
 fn row_to_string(row: &postgres::row::Row, query: &str) -> String {
     let mut line = String::new();
 
@@ -635,8 +1243,7 @@ fn row_to_string(row: &postgres::row::Row, query: &str) -> String {
                         App::error(
                             format!("Type \"{}\" for column \"{}\" not handled. \
                         Query:\n{}",
-                        $col.type_(), $col.name(), query));
-                        format!("unhandled")
+                        $col.type_(), $col.name(), query))
                     },
             };
             line.push_str(&format!("{}: {}\n", $col.name(), val));
@@ -658,14 +1265,14 @@ fn row_to_string(row: &postgres::row::Row, query: &str) -> String {
             VARCHAR => String,
         );
     }
-    line.push_str("\n");
+    line.push('\n');
 
     line
 }
 
 #[cfg(test)]
 mod test {
-    use super::{compare::*, pgdiff::*, pgtype::*, *};
+    use super::{compare::*, glob_match, pgdiff::*, pgtype::*, update_graph_paths};
     use postgres::Row;
     use std::collections::BTreeMap;
 
@@ -751,6 +1358,78 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("*", "1.0"));
+        assert!(glob_match("1.*", "1.0"));
+        assert!(glob_match("1.?", "1.0"));
+        assert!(glob_match("*.1", "2.1"));
+        assert!(glob_match("1*1", "1.0.1"));
+        assert!(!glob_match("1.?", "1.10"));
+        assert!(!glob_match("2.*", "1.0"));
+        assert!(!glob_match("1.0", "1.0.1"));
+    }
+
+    fn get_graph(scripts: &[&str]) -> BTreeMap<String, Vec<String>> {
+        let mut edges: BTreeMap<String, Vec<String>> = BTreeMap::new();
+
+        for script in scripts {
+            let (source, target) = script.split_once("--").unwrap();
+            edges
+                .entry(String::from(source))
+                .or_default()
+                .push(String::from(target));
+        }
+
+        edges
+    }
+
+    fn get_path(path: &str) -> Vec<String> {
+        path.split("--").map(String::from).collect()
+    }
+
+    #[test]
+    fn test_update_graph_paths() {
+        // Direct update script and step by step scripts, with downgrades
+        let edges = get_graph(&[
+            "1.0--1.1", "1.0--1.2", "1.1--1.0", "1.1--1.2", "1.2--1.1", "1.2--1.3",
+        ]);
+
+        let (paths, truncated) =
+            update_graph_paths(&edges, "1.0", "1.3", &get_path("1.0--1.2--1.3"), 10);
+        assert!(!truncated);
+        assert_eq!(paths, vec![get_path("1.0--1.1--1.2--1.3")]);
+
+        // Downgrade paths can't go through the source version again
+        let (paths, _) = update_graph_paths(&edges, "1.2", "1.0", &get_path("1.2--1.1--1.0"), 10);
+        assert!(paths.is_empty(), "{:?}", paths);
+
+        // No path at all
+        let (paths, truncated) = update_graph_paths(&edges, "1.3", "1.0", &[], 10);
+        assert!(paths.is_empty() && !truncated);
+
+        // Every version can be updated to any later version, the number of
+        // paths is exponential.
+        let versions: Vec<String> = (0..20).map(|i| format!("1.{}", i)).collect();
+        let mut scripts = vec![];
+        for (i, source) in versions.iter().enumerate() {
+            for target in &versions[i + 1..] {
+                scripts.push(format!("{}--{}", source, target));
+                scripts.push(format!("{}--{}", target, source));
+            }
+        }
+        let scripts: Vec<&str> = scripts.iter().map(String::as_str).collect();
+        let edges = get_graph(&scripts);
+
+        let (paths, truncated) =
+            update_graph_paths(&edges, "1.0", "1.19", &get_path("1.0--1.19"), 50);
+        assert!(truncated);
+        assert_eq!(paths.len(), 50);
+        assert!(paths
+            .iter()
+            .all(|p| p[0] == "1.0" && p.last().unwrap() == "1.19"));
+    }
+
     #[test]
     fn test_tlist() {
         let t1_tlist = PgClass::tlist(PG_10);
@@ -815,7 +1494,7 @@ mod test {
             msg.is_none(),
             "Identical relation (v14) should not raise \
             anything\n{}",
-            msg.unwrap().to_string()
+            msg.unwrap()
         );
 
         let t1 = get_t1(430000);
@@ -826,7 +1505,7 @@ mod test {
             msg.is_none(),
             "Identical relation (v43) should not raise \
             anything\n{}",
-            msg.unwrap().to_string()
+            msg.unwrap()
         );
     }
 
@@ -908,8 +1587,7 @@ mod test {
             .expect("Should find differences")
             .to_string();
 
-        assert_eq!(
-            true,
+        assert!(
             msg.contains("for Relation t1")
                 && msg.contains("in new_feature")
                 && msg.contains("installed has no value")
@@ -927,8 +1605,7 @@ mod test {
             .expect("Should find differences")
             .to_string();
 
-        assert_eq!(
-            true,
+        assert!(
             msg.contains("for Relation t1")
                 && msg.contains("in new_feature")
                 && msg.contains("upgraded has no value")
@@ -986,30 +1663,19 @@ mod test {
 
         let msg = ext_ins.compare(&ext_ins);
 
-        match msg {
-            Some(m) => {
-                assert!(
-                    false,
-                    "Two empty extensions should be identical\n{}",
-                    m.to_string()
-                );
-            }
-            None => {}
+        if let Some(m) = msg {
+            panic!("Two empty extensions should be identical\n{}", m);
         }
 
         let ext_ins = get_extension("empty_ext", Some(vec![]));
 
         let msg = ext_ins.compare(&ext_ins);
 
-        match msg {
-            Some(m) => {
-                assert!(
-                    false,
-                    "Two extensions with empty rel list should be identical\n{}",
-                    m.to_string()
-                );
-            }
-            None => {}
+        if let Some(m) = msg {
+            panic!(
+                "Two extensions with empty rel list should be identical\n{}",
+                m
+            );
         }
 
         let t1 = get_t1(PG_14);
@@ -1017,15 +1683,8 @@ mod test {
 
         let msg = ext_ins.compare(&ext_ins);
 
-        match msg {
-            Some(m) => {
-                assert!(
-                    false,
-                    "Two extensions with same 1 rel should be identical\n{}",
-                    m.to_string()
-                );
-            }
-            None => {}
+        if let Some(m) = msg {
+            panic!("Two extensions with same 1 rel should be identical\n{}", m);
         }
     }
 
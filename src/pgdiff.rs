@@ -1,6 +1,6 @@
 /*----------------------------------------------------------------------------
  * Author: Julien Rouhaud
-     * Copyright: Copyright (c) 2021-2026 : Julien Rouhaud - All rights reserved
+ * Copyright: Copyright (c) 2021-2026 : Julien Rouhaud - All rights reserved
  *---------------------------------------------------------------------------*/
 use diffy::Patch;
 use std::cmp::Ordering;
@@ -251,7 +251,7 @@ impl<'a> SchemaDiff<'a> {
                     res.push_str(&format!("{i} - {} changed to: {}\n", guc, val, i = ind0,));
                 }
 
-                res.push_str("\n");
+                res.push('\n');
 
                 res
             }
@@ -259,8 +259,8 @@ impl<'a> SchemaDiff<'a> {
     }
 }
 
-impl<'a> ToString for SchemaDiff<'a> {
-    fn to_string(&self) -> String {
-        self.decode(0)
+impl<'a> std::fmt::Display for SchemaDiff<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.decode(0))
     }
 }
@@ -71,6 +71,7 @@ macro_rules! PgAlias {
 // https://docs.rs/postgres-types/0.2.1/postgres_types/trait.FromSql.html
 PgAlias! {
     Bool = bool,
+    Bigint = i64,
     Integer = i32,
     Real = f32,
     Smallint = i16,
@@ -176,6 +177,67 @@ impl<'a> Compare<'a> for ClassOptions {
     }
 }
 
+// Used for text[] column storing sets of provider=label, as generated by the
+// seclabels!() macro.
+#[derive(Debug)]
+pub struct SecLabels {
+    labels: BTreeMap<String, String>,
+}
+
+impl SecLabels {
+    // Labels can contain "=", so only split on the first one
+    fn from_vec(vec: Vec<String>) -> SecLabels {
+        let mut labels = BTreeMap::new();
+
+        for e in vec.iter() {
+            match e.split_once('=') {
+                Some((provider, label)) => {
+                    labels.insert(String::from(provider), String::from(label));
+                }
+                None => {
+                    panic!("Expected provider=label format, found {}", e);
+                }
+            }
+        }
+
+        SecLabels { labels }
+    }
+}
+
+impl<'a> FromSql<'a> for SecLabels {
+    fn from_sql(
+        ty: &Type,
+        raw: &'a [u8],
+    ) -> Result<SecLabels, Box<dyn std::error::Error + Sync + Send>> {
+        let vec = Vec::<String>::from_sql(ty, raw)?;
+
+        Ok(SecLabels::from_vec(vec))
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        Vec::<String>::accepts(ty)
+    }
+}
+
+impl<'a> Compare<'a> for SecLabels {
+    fn compare(&'a self, other: &'a Self) -> Option<SchemaDiff<'a>> {
+        compare_map(
+            &self.labels,
+            &other.labels,
+            "SecurityLabel",
+            Some(class_options_cmp),
+        )
+    }
+
+    fn value(&self) -> String {
+        self.labels
+            .iter()
+            .map(|(k, v)| format!("{}={}", k, v))
+            .collect::<Vec<String>>()
+            .join(",")
+    }
+}
+
 // Used for unordered array
 #[derive(Debug)]
 pub struct List {
@@ -284,3 +346,41 @@ impl<'a> Compare<'a> for Guc {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::SecLabels;
+    use crate::compare::Compare;
+
+    #[test]
+    fn parse_seclabels() {
+        let labels = SecLabels::from_vec(vec![
+            String::from("dummy=classified"),
+            String::from("selinux=system_u:object_r:sepgsql_table_t:s0=x"),
+        ]);
+
+        assert_eq!(labels.labels["dummy"], "classified");
+        assert_eq!(
+            labels.labels["selinux"],
+            "system_u:object_r:sepgsql_table_t:s0=x"
+        );
+
+        let other = SecLabels::from_vec(vec![
+            String::from("dummy=classified"),
+            String::from("selinux=system_u:object_r:sepgsql_table_t:s0=y"),
+        ]);
+
+        let msg = labels
+            .compare(&other)
+            .expect("Should find differences")
+            .to_string();
+
+        assert!(
+            msg.contains("- system_u:object_r:sepgsql_table_t:s0=x")
+                && msg.contains("+ system_u:object_r:sepgsql_table_t:s0=y")
+                && !msg.contains("dummy"),
+            "Only the selinux label change should be detected\n{}",
+            msg
+        );
+    }
+}
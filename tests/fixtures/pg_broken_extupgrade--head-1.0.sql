@@ -29,6 +29,8 @@ CREATE TABLE ref(id integer);
 CREATE TABLE main2(id integer primary key, val text CHECK (length(val) > 1));
 COMMENT ON CONSTRAINT main2_pkey ON main2 IS 'more than 1';
 CREATE TABLE ref2(id integer references main2 (id) ON UPDATE no action ON DELETE restrict);
+-- identical foreign key, its internal triggers shouldn't be reported
+CREATE TABLE ref3(id integer REFERENCES main2 (id));
 CREATE TABLE options_1(id integer);
 ALTER TABLE options_1 SET (fillfactor = 80, toast_tuple_target = 8100);
 CREATE TABLE options_2(id integer);
@@ -118,3 +120,5 @@ CREATE OPERATOR CLASS my_opc1 FOR TYPE int4 USING gin AS
 CREATE OPERATOR CLASS my_opc1 FOR TYPE int4 USING gist AS
     OPERATOR 1 = (int4, int4),
     FUNCTION 1 btint4cmp(int4, int4);
+CREATE OPERATOR FAMILY my_opf_members USING btree;
+COMMENT ON OPERATOR CLASS my_opc1 USING btree IS 'renamed';
@@ -30,6 +30,8 @@ CREATE TABLE ref(id integer REFERENCES main (id));
 CREATE TABLE main2(id integer PRIMARY KEY, val text CHECK (length(val) > 2));
 COMMENT ON CONSTRAINT main2_pkey ON main2 IS 'more than 2';
 CREATE TABLE ref2(id integer references main2 (id) ON UPDATE cascade ON DELETE cascade);
+-- identical foreign key, its internal triggers shouldn't be reported
+CREATE TABLE ref3(id integer REFERENCES main2 (id));
 CREATE TABLE options_1(id integer);
 ALTER TABLE options_1 SET (autovacuum_enabled = off, fillfactor = 90, parallel_workers = 4);
 CREATE TABLE options_2(id integer);
@@ -131,3 +133,8 @@ CREATE OPERATOR CLASS my_opc2 FOR TYPE int4 USING gin AS
 CREATE OPERATOR CLASS my_opc2 FOR TYPE int4 USING gist AS
     OPERATOR 1 = (int4, int4),
     FUNCTION 1 btint4cmp(int4, int4);
+CREATE OPERATOR FAMILY my_opf_members USING btree;
+ALTER OPERATOR FAMILY my_opf_members USING btree ADD
+    OPERATOR 1 < (int4, int8),
+    FUNCTION 1 btint48cmp(int4, int8);
+COMMENT ON OPERATOR CLASS my_opc2 USING btree IS 'created';